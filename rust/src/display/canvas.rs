@@ -1,6 +1,7 @@
 use crate::display::color::Color;
 use crate::display::resolution::Resolution;
 
+#[derive(Debug, Clone)]
 pub struct Canvas {
     pub width: usize,
     pub height: usize,
@@ -55,6 +55,23 @@ impl Color {
         }
     }
 
+    /// The largest difference between the two colors in any one channel.
+    pub fn difference(&self, other: &Color) -> f64 {
+        (self.red - other.red)
+            .abs()
+            .max((self.green - other.green).abs())
+            .max((self.blue - other.blue).abs())
+    }
+
+    /// The value of the brightest channel.
+    pub fn max_channel(&self) -> f64 {
+        self.red.max(self.green).max(self.blue)
+    }
+
+    pub fn channels(&self) -> (f64, f64, f64) {
+        (self.red, self.green, self.blue)
+    }
+
     pub fn to255(self) -> (u64, u64, u64) {
         (
             value_to_255(self.red),
@@ -0,0 +1,48 @@
+use crate::display::canvas::Canvas;
+use crate::display::color::Color;
+use crate::display::resolution::Resolution;
+use image::ImageResult;
+
+/// Read an image file (PNG, JPEG or any other format the image crate supports) into a Canvas.
+pub fn read_image(file_name: &str) -> ImageResult<Canvas> {
+    let image = image::open(file_name)?.to_rgb8();
+    let resolution = Resolution::new(image.width() as usize, image.height() as usize);
+
+    let mut canvas = Canvas::new(resolution);
+    for (x, y, pixel) in image.enumerate_pixels() {
+        let [red, green, blue] = pixel.0;
+        let color = Color::new(
+            red as f64 / 255.0,
+            green as f64 / 255.0,
+            blue as f64 / 255.0,
+        );
+        canvas.write_pixel(x as usize, y as usize, color);
+    }
+    Ok(canvas)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::display::image_reader::read_image;
+    use image::RgbImage;
+
+    #[test]
+    fn reading_image_into_canvas() {
+        let mut image = RgbImage::new(2, 3);
+        image.put_pixel(1, 2, image::Rgb([255, 0, 51]));
+        let file_name = std::env::temp_dir().join("rtc_image_reader_test.png");
+        image.save(&file_name).unwrap();
+
+        let canvas = read_image(file_name.to_str().unwrap()).unwrap();
+        assert_eq!(2, canvas.width);
+        assert_eq!(3, canvas.height);
+        assert_eq!(Color::new(1.0, 0.0, 0.2), canvas.pixel_at(1, 2));
+        assert_eq!(Color::BLACK, canvas.pixel_at(0, 0));
+    }
+
+    #[test]
+    fn reading_missing_image_fails() {
+        assert!(read_image("no/such/image.png").is_err());
+    }
+}
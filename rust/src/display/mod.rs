@@ -1,4 +1,5 @@
 pub mod canvas;
 pub mod color;
+pub mod image_reader;
 pub mod ppm_writer;
 pub mod resolution;
@@ -2,12 +2,15 @@ use crate::display::color::Color;
 use crate::exercises::material_helpers;
 use crate::geometry::point::Point;
 use crate::geometry::transformations;
+use crate::geometry::transformations::translation;
 use crate::geometry::vector::Vector;
 use crate::tracing::camera::{Camera, CameraMaker};
 use crate::tracing::material::Material;
 use crate::tracing::patterns::checkers::Checkers;
 use crate::tracing::point_light::PointLight;
+use crate::tracing::sampling::PixelSampling;
 use crate::tracing::shapes::cube::Cube;
+use crate::tracing::shapes::group::Group;
 use crate::tracing::shapes::shape::{Shape, ShapeGeometry};
 use crate::tracing::world::World;
 use std::f64::consts::PI;
@@ -43,57 +46,29 @@ pub fn make_world() -> (World, CameraMaker) {
     let pawn_elevation = 0.75;
     let back_row_elevation = 1.0;
 
-    let rotation = transformations::rotation_y(PI / 2.0);
-
-    objects.push(
+    let pieces: Vec<Shape> = vec![
         Shape::sphere()
-            .with_transform(
-                pawn_scaling
-                    .then(&transformations::translation(1.5, pawn_elevation, -2.5))
-                    .then(&rotation),
-            )
+            .with_transform(pawn_scaling.then(&translation(1.5, pawn_elevation, -2.5)))
             .with_material(material_helpers::colored_glass(Color::WHITE)),
-    );
-
-    objects.push(
         Shape::sphere()
-            .with_transform(
-                pawn_scaling
-                    .then(&transformations::translation(2.5, pawn_elevation, -0.5))
-                    .then(&rotation),
-            )
+            .with_transform(pawn_scaling.then(&translation(2.5, pawn_elevation, -0.5)))
             .with_material(material_helpers::colored_glass(Color::RED)),
-    );
-
-    objects.push(
         Shape::sphere()
-            .with_transform(
-                pawn_scaling
-                    .then(&transformations::translation(1.5, pawn_elevation, 1.5))
-                    .then(&rotation),
-            )
+            .with_transform(pawn_scaling.then(&translation(1.5, pawn_elevation, 1.5)))
             .with_material(material_helpers::colored_glass(Color::RED)),
-    );
-
-    objects.push(
         Shape::sphere()
-            .with_transform(
-                back_row_scaling
-                    .then(&transformations::translation(0.5, back_row_elevation, 0.5))
-                    .then(&rotation),
-            )
+            .with_transform(back_row_scaling.then(&translation(0.5, back_row_elevation, 0.5)))
             .with_material(material_helpers::colored_glass(Color::RED)),
-    );
-
-    objects.push(
         Shape::sphere()
-            .with_transform(
-                back_row_scaling
-                    .then(&transformations::translation(2.5, back_row_elevation, 1.5))
-                    .then(&rotation),
-            )
+            .with_transform(back_row_scaling.then(&translation(2.5, back_row_elevation, 1.5)))
             .with_material(material_helpers::colored_glass(Color::WHITE)),
-    );
+    ];
+
+    // The whole set of pieces is turned to face the camera.
+    let pieces = Group::new(pieces)
+        .into_shape()
+        .with_transform(transformations::rotation_y(PI / 2.0));
+    objects.push(pieces);
 
     (World::new(objects, light_source), camera_maker())
 }
@@ -104,5 +79,10 @@ fn camera_maker() -> CameraMaker {
         Point::at(0, 1, 0),
         Vector::new(0, 1, 0),
     );
-    Box::new(|resolution| Camera::new(resolution, PI / 3.0, camera_transform))
+    Box::new(|resolution| {
+        Camera::new(resolution, PI / 3.0, camera_transform).with_sampling(PixelSampling::Adaptive {
+            threshold: 0.1,
+            max_depth: 2,
+        })
+    })
 }
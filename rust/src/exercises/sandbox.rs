@@ -1,6 +1,7 @@
 use crate::display::color::Color;
 use crate::exercises::snapshot;
 use crate::geometry::transformations;
+use crate::tracing::backgrounds::vertical_gradient::VerticalGradient;
 use crate::tracing::camera::CameraMaker;
 use crate::tracing::material::Material;
 use crate::tracing::patterns::checkers::Checkers;
@@ -35,7 +36,8 @@ pub fn make_world() -> (World, CameraMaker) {
     let objects: Vec<Shape> = vec![floor, object];
 
     (
-        World::new(objects, light_source),
+        World::new(objects, light_source)
+            .with_background(VerticalGradient::new(Color::WHITE, Color::LIGHT_BLUE)),
         snapshot::camera_one_maker(),
     )
 }
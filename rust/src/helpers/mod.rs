@@ -1,2 +1,3 @@
 pub mod approximate;
 pub mod general;
+pub mod noise;
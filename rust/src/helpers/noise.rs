@@ -0,0 +1,234 @@
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+
+// Ken Perlin's reference permutation of 0..=255, which the lattice gradients are hashed with.
+const PERMUTATION: [u8; 256] = [
+    151, 160, 137, 91, 90, 15, 131, 13, 201, 95, 96, 53, 194, 233, 7, 225, 140, 36, 103, 30, 69,
+    142, 8, 99, 37, 240, 21, 10, 23, 190, 6, 148, 247, 120, 234, 75, 0, 26, 197, 62, 94, 252, 219,
+    203, 117, 35, 11, 32, 57, 177, 33, 88, 237, 149, 56, 87, 174, 20, 125, 136, 171, 168, 68, 175,
+    74, 165, 71, 134, 139, 48, 27, 166, 77, 146, 158, 231, 83, 111, 229, 122, 60, 211, 133, 230,
+    220, 105, 92, 41, 55, 46, 245, 40, 244, 102, 143, 54, 65, 25, 63, 161, 1, 216, 80, 73, 209, 76,
+    132, 187, 208, 89, 18, 169, 200, 196, 135, 130, 116, 188, 159, 86, 164, 100, 109, 198, 173,
+    186, 3, 64, 52, 217, 226, 250, 124, 123, 5, 202, 38, 147, 118, 126, 255, 82, 85, 212, 207, 206,
+    59, 227, 47, 16, 58, 17, 182, 189, 28, 42, 223, 183, 170, 213, 119, 248, 152, 2, 44, 154, 163,
+    70, 221, 153, 101, 155, 167, 43, 172, 9, 129, 22, 39, 253, 19, 98, 108, 110, 79, 113, 224, 232,
+    178, 185, 112, 104, 218, 246, 97, 228, 251, 34, 242, 193, 238, 210, 144, 12, 191, 179, 162,
+    241, 81, 51, 145, 235, 249, 14, 239, 107, 49, 192, 214, 31, 181, 199, 106, 157, 184, 84, 204,
+    176, 115, 121, 50, 45, 127, 4, 150, 254, 138, 236, 205, 93, 222, 114, 67, 29, 24, 72, 243, 141,
+    128, 195, 78, 66, 215, 61, 156, 180,
+];
+
+/**
+ * (Improved) Perlin gradient noise: smoothly varying pseudo-random values, roughly between -1.0
+ * and 1.0, with features about one unit across. Always zero at points with integer coordinates.
+ */
+pub fn perlin(point: Point) -> f64 {
+    let hash = |index: usize| PERMUTATION[index & 255] as usize;
+
+    // The unit cube containing the point, and where the point lies within it.
+    let (xi, yi, zi) = (
+        point.x.floor() as i64 as usize & 255,
+        point.y.floor() as i64 as usize & 255,
+        point.z.floor() as i64 as usize & 255,
+    );
+    let (x, y, z) = (
+        point.x - point.x.floor(),
+        point.y - point.y.floor(),
+        point.z - point.z.floor(),
+    );
+    let (u, v, w) = (fade(x), fade(y), fade(z));
+
+    // Hash each of the cube's corners...
+    let a = hash(xi) + yi;
+    let aa = hash(a) + zi;
+    let ab = hash(a + 1) + zi;
+    let b = hash(xi + 1) + yi;
+    let ba = hash(b) + zi;
+    let bb = hash(b + 1) + zi;
+
+    // ...and blend the contributions of their gradients.
+    lerp(
+        w,
+        lerp(
+            v,
+            lerp(
+                u,
+                gradient(hash(aa), x, y, z),
+                gradient(hash(ba), x - 1.0, y, z),
+            ),
+            lerp(
+                u,
+                gradient(hash(ab), x, y - 1.0, z),
+                gradient(hash(bb), x - 1.0, y - 1.0, z),
+            ),
+        ),
+        lerp(
+            v,
+            lerp(
+                u,
+                gradient(hash(aa + 1), x, y, z - 1.0),
+                gradient(hash(ba + 1), x - 1.0, y, z - 1.0),
+            ),
+            lerp(
+                u,
+                gradient(hash(ab + 1), x, y - 1.0, z - 1.0),
+                gradient(hash(bb + 1), x - 1.0, y - 1.0, z - 1.0),
+            ),
+        ),
+    )
+}
+
+/// A vector of three independent components of noise, e.g. for jittering points or normals.
+pub fn perlin_vector(point: Point) -> Vector {
+    // Sample the noise at well separated points, so each axis moves independently.
+    let offset_by =
+        |x: f64, y: f64, z: f64| perlin(Point::at(point.x + x, point.y + y, point.z + z));
+    Vector::new(
+        offset_by(0.0, 0.0, 0.0),
+        offset_by(5.2, 1.3, 7.1),
+        offset_by(1.7, 9.2, 3.4),
+    )
+}
+
+/**
+ * Fractal Brownian motion: octaves layers of noise, each at twice the frequency and half the
+ * amplitude of the last, giving detail at every scale (e.g. clouds). Roughly -1.0 to 1.0.
+ */
+pub fn fractal_brownian_motion(point: Point, octaves: usize) -> f64 {
+    fractal_sum(point, octaves, perlin)
+}
+
+/// Like fractal_brownian_motion, but summing the absolute noise, giving sharp creases (e.g. fire,
+/// or the veins in marble). Roughly 0.0 to 1.0.
+pub fn turbulence(point: Point, octaves: usize) -> f64 {
+    fractal_sum(point, octaves, |point| perlin(point).abs())
+}
+
+// Sum the octaves of noise provided, scaled back to the range of a single octave.
+fn fractal_sum(point: Point, octaves: usize, noise: impl Fn(Point) -> f64) -> f64 {
+    let mut total = 0.0;
+    let mut amplitudes = 0.0;
+    let mut frequency = 1.0;
+    let mut amplitude = 1.0;
+
+    for _ in 0..octaves {
+        let scaled = Point::at(
+            point.x * frequency,
+            point.y * frequency,
+            point.z * frequency,
+        );
+        total += noise(scaled) * amplitude;
+        amplitudes += amplitude;
+        frequency *= 2.0;
+        amplitude /= 2.0;
+    }
+
+    if amplitudes == 0.0 {
+        0.0
+    } else {
+        total / amplitudes
+    }
+}
+
+// Ease curve, so the noise changes smoothly across the faces of the lattice cubes.
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+// Dot product of (x, y, z) with one of 12 gradient directions, chosen by the hash.
+fn gradient(hash: usize, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    let u = if h & 1 == 0 { u } else { -u };
+    let v = if h & 2 == 0 { v } else { -v };
+    u + v
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::point::Point;
+    use crate::helpers::noise::{fractal_brownian_motion, perlin, turbulence, PERMUTATION};
+    use rand::prelude::*;
+
+    // Each gradient is the sum of two offsets within a unit cube, and blending them never
+    // goes beyond the largest, so noise can't pass this (though it rarely gets near).
+    const NOISE_BOUND: f64 = 2.0;
+
+    fn random_points() -> Vec<Point> {
+        let mut rng = StdRng::seed_from_u64(1234);
+        (0..500)
+            .map(|_| {
+                Point::at(
+                    rng.gen_range(-50.0..50.0),
+                    rng.gen_range(-50.0..50.0),
+                    rng.gen_range(-50.0..50.0),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn permutation_contains_every_byte_once() {
+        let mut values: Vec<u8> = PERMUTATION.to_vec();
+        values.sort_unstable();
+        assert!(values
+            .iter()
+            .enumerate()
+            .all(|(i, value)| i == *value as usize));
+    }
+
+    #[test]
+    fn noise_is_zero_on_lattice_points() {
+        let cases = vec![Point::origin(), Point::at(1, 2, 3), Point::at(-4, 7, -300)];
+        for point in cases {
+            assert_eq!(0.0, perlin(point));
+        }
+    }
+
+    #[test]
+    fn noise_varies_smoothly_within_range() {
+        let mut varies = false;
+        for point in random_points() {
+            let value = perlin(point);
+            assert!(value.abs() <= NOISE_BOUND);
+
+            let nearby = perlin(Point::at(point.x + 0.001, point.y, point.z));
+            assert!((value - nearby).abs() < 0.01);
+            varies |= value.abs() > 0.1;
+        }
+        assert!(varies);
+    }
+
+    #[test]
+    fn noise_is_repeatable() {
+        let point = Point::at(1.3, -2.7, 0.45);
+        assert_eq!(perlin(point), perlin(point));
+        assert_ne!(perlin(point), perlin(Point::at(1.3, -2.7, 0.55)));
+    }
+
+    #[test]
+    fn fractal_noise_stays_within_range() {
+        for point in random_points() {
+            assert!(fractal_brownian_motion(point, 4).abs() <= NOISE_BOUND);
+            assert!((0.0..=NOISE_BOUND).contains(&turbulence(point, 4)));
+        }
+    }
+
+    #[test]
+    fn single_octave_of_fractal_noise_is_plain_noise() {
+        let point = Point::at(0.3, 1.7, -2.2);
+        assert_eq!(perlin(point), fractal_brownian_motion(point, 1));
+        assert_eq!(perlin(point).abs(), turbulence(point, 1));
+    }
+}
@@ -0,0 +1,25 @@
+/**
+ * Settings for ambient occlusion: darkening the ambient light in creases and corners, and where
+ * objects rest on others, by casting rays out from each hit to see how much of the sky it can see.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmbientOcclusion {
+    // How many rays to cast from each hit. More give smoother results.
+    samples: usize,
+    // Anything further away than this is considered not to block the ambient light.
+    distance: f64,
+}
+
+impl AmbientOcclusion {
+    pub fn new(samples: usize, distance: f64) -> AmbientOcclusion {
+        AmbientOcclusion { samples, distance }
+    }
+
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+}
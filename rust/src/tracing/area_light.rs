@@ -0,0 +1,163 @@
+use rand::prelude::*;
+
+use crate::display::color::Color;
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::tracing::light::{Light, LightSample};
+
+/**
+ * A rectangular light, sampled over a grid of cells spanning the two edges from its corner.
+ *
+ * Each sample is taken at a random point within its cell (jitter), which trades the banding
+ * a regular grid would produce in penumbrae for a little noise.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct AreaLight {
+    corner: Point,
+    u_step: Vector,
+    u_steps: usize,
+    v_step: Vector,
+    v_steps: usize,
+    intensity: Color,
+    jittered: bool,
+}
+
+impl AreaLight {
+    /// The light is split into u_steps by v_steps cells, with at least one along each edge.
+    pub fn new(
+        corner: Point,
+        full_u: Vector,
+        u_steps: usize,
+        full_v: Vector,
+        v_steps: usize,
+        intensity: Color,
+    ) -> AreaLight {
+        let u_steps = u_steps.max(1);
+        let v_steps = v_steps.max(1);
+        AreaLight {
+            corner,
+            u_step: full_u / u_steps as f64,
+            u_steps,
+            v_step: full_v / v_steps as f64,
+            v_steps,
+            intensity,
+            jittered: true,
+        }
+    }
+
+    /// Sample the center of each cell instead, giving repeatable (if banded) results.
+    pub fn without_jitter(self) -> AreaLight {
+        AreaLight {
+            jittered: false,
+            ..self
+        }
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.u_steps * self.v_steps
+    }
+
+    /// A point within each cell of the light, jittered unless disabled.
+    pub fn sample_points(&self) -> Vec<Point> {
+        let mut rng = thread_rng();
+        let mut offset = || {
+            if self.jittered {
+                rng.gen_range(0.0..1.0)
+            } else {
+                0.5
+            }
+        };
+
+        let mut points = Vec::with_capacity(self.cell_count());
+        for v in 0..self.v_steps {
+            for u in 0..self.u_steps {
+                points.push(self.point_on_light(u, v, offset(), offset()));
+            }
+        }
+        points
+    }
+
+    /// The point within cell (u, v), offset from the cell's corner by the fractions provided.
+    fn point_on_light(&self, u: usize, v: usize, u_offset: f64, v_offset: f64) -> Point {
+        self.corner + self.u_step * (u as f64 + u_offset) + self.v_step * (v as f64 + v_offset)
+    }
+}
+
+impl Light for AreaLight {
+    fn intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn samples_from(&self, point: Point) -> Vec<LightSample> {
+        self.sample_points()
+            .into_iter()
+            .map(|light_point| LightSample::between(point, light_point))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::geometry::point::Point;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::area_light::AreaLight;
+
+    fn light() -> AreaLight {
+        AreaLight::new(
+            Point::origin(),
+            Vector::new(2, 0, 0),
+            4,
+            Vector::new(0, 0, 1),
+            2,
+            Color::WHITE,
+        )
+    }
+
+    #[test]
+    fn creating_area_light() {
+        let light = light();
+        assert_eq!(Vector::new(0.5, 0.0, 0.0), light.u_step);
+        assert_eq!(Vector::new(0.0, 0.0, 0.5), light.v_step);
+        assert_eq!(8, light.cell_count());
+    }
+
+    #[test]
+    fn area_light_has_at_least_one_cell() {
+        let light = AreaLight::new(
+            Point::origin(),
+            Vector::new(2, 0, 0),
+            0,
+            Vector::new(0, 0, 1),
+            0,
+            Color::WHITE,
+        )
+        .without_jitter();
+
+        assert_eq!(1, light.cell_count());
+        assert_eq!(vec![Point::at(1.0, 0.0, 0.5)], light.sample_points());
+    }
+
+    #[test]
+    fn sampling_cell_centers() {
+        let samples = light().without_jitter().sample_points();
+
+        assert_eq!(8, samples.len());
+        assert_eq!(Point::at(0.25, 0.0, 0.25), samples[0]);
+        assert_eq!(Point::at(0.75, 0.0, 0.25), samples[1]);
+        assert_eq!(Point::at(0.25, 0.0, 0.75), samples[4]);
+        assert_eq!(Point::at(1.75, 0.0, 0.75), samples[7]);
+    }
+
+    #[test]
+    fn jittered_samples_stay_within_their_cells() {
+        let samples = light().sample_points();
+
+        for (index, sample) in samples.iter().enumerate() {
+            let (u, v) = ((index % 4) as f64, (index / 4) as f64);
+            assert!(sample.x >= u * 0.5 && sample.x <= (u + 1.0) * 0.5);
+            assert!(sample.z >= v * 0.5 && sample.z <= (v + 1.0) * 0.5);
+            assert_eq!(0.0, sample.y);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::fmt::Debug;
+
+use crate::display::color::Color;
+use crate::geometry::vector::Vector;
+
+/**
+ * Background is a Strategy giving the color of the (infinitely distant) surroundings of a World,
+ * seen by any ray that misses everything in it.
+ */
+pub trait Background: Send + Sync + Debug {
+    /// The color seen looking in the (normalized) direction provided.
+    fn color_in(&self, direction: Vector) -> Color;
+}
+
+/// The same color in every direction.
+impl Background for Color {
+    fn color_in(&self, _direction: Vector) -> Color {
+        *self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::backgrounds::background::Background;
+
+    #[test]
+    fn color_background_is_constant() {
+        let background = Color::new(0.2, 0.4, 0.6);
+        assert_eq!(background, background.color_in(Vector::new(0, 1, 0)));
+        assert_eq!(background, background.color_in(Vector::new(1, 0, 0)));
+    }
+}
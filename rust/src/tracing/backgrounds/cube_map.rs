@@ -0,0 +1,129 @@
+use crate::display::canvas::Canvas;
+use crate::display::color::Color;
+use crate::geometry::vector::Vector;
+use crate::tracing::backgrounds::background::Background;
+
+/**
+ * A skybox: six images on the faces of a cube around the World, each as seen looking out at it
+ * from the center with +y up. Front is -z (straight ahead for an untransformed Camera), so left
+ * is +x. The up face's bottom edge and the down face's top edge both meet the front face.
+ */
+#[derive(Debug, Clone)]
+pub struct CubeMap {
+    front: Canvas,
+    back: Canvas,
+    left: Canvas,
+    right: Canvas,
+    up: Canvas,
+    down: Canvas,
+}
+
+impl CubeMap {
+    pub fn new(
+        front: Canvas,
+        back: Canvas,
+        left: Canvas,
+        right: Canvas,
+        up: Canvas,
+        down: Canvas,
+    ) -> CubeMap {
+        CubeMap {
+            front,
+            back,
+            left,
+            right,
+            up,
+            down,
+        }
+    }
+
+    /// The face seen in the direction provided, and where on it (u right, v up, 0.0 to 1.0).
+    fn face_for(&self, direction: Vector) -> (&Canvas, f64, f64) {
+        let largest = direction
+            .x
+            .abs()
+            .max(direction.y.abs())
+            .max(direction.z.abs());
+        // The point where the direction pierces the cube from -1 to 1 on each axis.
+        let (x, y, z) = (
+            direction.x / largest,
+            direction.y / largest,
+            direction.z / largest,
+        );
+
+        if largest == direction.x {
+            (&self.left, (1.0 - z) / 2.0, (y + 1.0) / 2.0)
+        } else if largest == -direction.x {
+            (&self.right, (z + 1.0) / 2.0, (y + 1.0) / 2.0)
+        } else if largest == direction.y {
+            (&self.up, (1.0 - x) / 2.0, (z + 1.0) / 2.0)
+        } else if largest == -direction.y {
+            (&self.down, (1.0 - x) / 2.0, (1.0 - z) / 2.0)
+        } else if largest == -direction.z {
+            (&self.front, (1.0 - x) / 2.0, (y + 1.0) / 2.0)
+        } else {
+            (&self.back, (x + 1.0) / 2.0, (y + 1.0) / 2.0)
+        }
+    }
+}
+
+impl Background for CubeMap {
+    fn color_in(&self, direction: Vector) -> Color {
+        let (face, u, v) = self.face_for(direction);
+
+        let x = (u * face.width as f64) as usize;
+        let y = ((1.0 - v) * face.height as f64) as usize;
+        face.pixel_at(x.min(face.width - 1), y.min(face.height - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::canvas::Canvas;
+    use crate::display::color::Color;
+    use crate::display::resolution::Resolution;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::backgrounds::background::Background;
+    use crate::tracing::backgrounds::cube_map::CubeMap;
+
+    fn face(color: Color) -> Canvas {
+        let mut canvas = Canvas::new(Resolution::new(2, 2));
+        // Mark the top left corner of each face.
+        canvas.write_pixel(0, 0, color);
+        canvas
+    }
+
+    #[test]
+    fn cube_map_picks_face_by_direction() {
+        let colors = [
+            Color::RED,
+            Color::WHITE,
+            Color::LIGHT_BLUE,
+            Color::PURPLE,
+            Color::LIGHT_GREEN,
+            Color::MUSTARD_YELLOW,
+        ];
+        let background = CubeMap::new(
+            face(colors[0]),
+            face(colors[1]),
+            face(colors[2]),
+            face(colors[3]),
+            face(colors[4]),
+            face(colors[5]),
+        );
+
+        // The top left corner of each face, looking out from the center.
+        let cases = vec![
+            (Vector::new(0.5, 0.5, -1.0), colors[0]),
+            (Vector::new(-0.5, 0.5, 1.0), colors[1]),
+            (Vector::new(1.0, 0.5, 0.5), colors[2]),
+            (Vector::new(-1.0, 0.5, -0.5), colors[3]),
+            (Vector::new(0.5, 1.0, 0.5), colors[4]),
+            (Vector::new(0.5, -1.0, -0.5), colors[5]),
+        ];
+        for (direction, expected) in cases {
+            assert_eq!(expected, background.color_in(direction.normalize()));
+            assert_eq!(Color::BLACK, background.color_in((-direction).normalize()));
+        }
+    }
+}
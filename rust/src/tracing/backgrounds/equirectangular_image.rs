@@ -0,0 +1,71 @@
+use crate::display::canvas::Canvas;
+use crate::display::color::Color;
+use crate::geometry::vector::Vector;
+use crate::tracing::backgrounds::background::Background;
+use std::f64::consts::PI;
+
+/**
+ * A panorama wrapped all the way around the World: longitude runs across the image and latitude
+ * down it, matching the Equirectangular projection. So -z is the center of the image.
+ */
+#[derive(Debug, Clone)]
+pub struct EquirectangularImage {
+    image: Canvas,
+}
+
+impl EquirectangularImage {
+    pub fn new(image: Canvas) -> EquirectangularImage {
+        EquirectangularImage { image }
+    }
+}
+
+impl Background for EquirectangularImage {
+    fn color_in(&self, direction: Vector) -> Color {
+        let longitude = direction.x.atan2(-direction.z);
+        let latitude = direction.y.clamp(-1.0, 1.0).asin();
+
+        let u = 0.5 - longitude / (2.0 * PI);
+        let v = 0.5 - latitude / PI;
+
+        let x = (u * self.image.width as f64) as usize;
+        let y = (v * self.image.height as f64) as usize;
+        self.image
+            .pixel_at(x.min(self.image.width - 1), y.min(self.image.height - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::canvas::Canvas;
+    use crate::display::color::Color;
+    use crate::display::resolution::Resolution;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::backgrounds::background::Background;
+    use crate::tracing::backgrounds::equirectangular_image::EquirectangularImage;
+
+    #[test]
+    fn equirectangular_image_wraps_around_world() {
+        // Columns left to right: behind, to the left (+x), ahead, to the right (-x).
+        let mut image = Canvas::new(Resolution::new(4, 2));
+        let columns = [Color::RED, Color::WHITE, Color::LIGHT_BLUE, Color::PURPLE];
+        for (x, color) in columns.iter().enumerate() {
+            image.write_pixel(x, 0, *color);
+        }
+        let background = EquirectangularImage::new(image);
+
+        let up = Vector::new(0.0, 0.2, 0.0);
+        let cases = vec![
+            (Vector::new(0, 0, -1), Color::LIGHT_BLUE),
+            (Vector::new(1, 0, 0), Color::WHITE),
+            (Vector::new(-1, 0, 0), Color::PURPLE),
+            (Vector::new(0, 0, 1), Color::RED),
+        ];
+        for (direction, expected) in cases {
+            assert_eq!(expected, background.color_in((direction + up).normalize()));
+            assert_eq!(
+                Color::BLACK,
+                background.color_in((direction - up).normalize())
+            );
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod background;
+pub mod cube_map;
+pub mod equirectangular_image;
+pub mod vertical_gradient;
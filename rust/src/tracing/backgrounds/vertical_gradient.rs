@@ -0,0 +1,43 @@
+use crate::display::color::Color;
+use crate::geometry::vector::Vector;
+use crate::tracing::backgrounds::background::Background;
+
+/// A sky fading from one color straight down to another straight up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerticalGradient {
+    bottom: Color,
+    top: Color,
+}
+
+impl VerticalGradient {
+    pub fn new(bottom: Color, top: Color) -> VerticalGradient {
+        VerticalGradient { bottom, top }
+    }
+}
+
+impl Background for VerticalGradient {
+    fn color_in(&self, direction: Vector) -> Color {
+        let fraction = (direction.y + 1.0) / 2.0;
+        self.bottom + (self.top - self.bottom) * fraction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::backgrounds::background::Background;
+    use crate::tracing::backgrounds::vertical_gradient::VerticalGradient;
+
+    #[test]
+    fn vertical_gradient_blends_by_height() {
+        let background = VerticalGradient::new(Color::WHITE, Color::LIGHT_BLUE);
+
+        assert_eq!(Color::LIGHT_BLUE, background.color_in(Vector::new(0, 1, 0)));
+        assert_eq!(Color::WHITE, background.color_in(Vector::new(0, -1, 0)));
+        assert_eq!(
+            Color::new(0.8, 0.9, 1.0),
+            background.color_in(Vector::new(0, 0, -1))
+        );
+    }
+}
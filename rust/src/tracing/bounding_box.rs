@@ -0,0 +1,336 @@
+use crate::geometry::matrix::Matrix;
+use crate::geometry::point::Point;
+use crate::helpers::approximate;
+use crate::helpers::general::OrderedTuple;
+use crate::tracing::ray::Ray;
+
+/**
+ * An axis-aligned box enclosing a shape. Used to cheaply rule out rays that cannot hit it.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    min: Point,
+    max: Point,
+}
+
+impl BoundingBox {
+    pub fn new(min: Point, max: Point) -> BoundingBox {
+        BoundingBox { min, max }
+    }
+
+    /// A box containing nothing. Adding anything to it produces that thing's bounds.
+    pub fn empty() -> BoundingBox {
+        BoundingBox::new(
+            Point::at(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            Point::at(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        )
+    }
+
+    /// A box containing everything, for shapes such as planes that go on forever.
+    pub fn infinite() -> BoundingBox {
+        BoundingBox::new(
+            Point::at(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            Point::at(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        )
+    }
+
+    /// The smallest box containing all of the points provided.
+    pub fn around(points: &[Point]) -> BoundingBox {
+        points.iter().fold(BoundingBox::empty(), |bounds, point| {
+            bounds.including(*point)
+        })
+    }
+
+    pub fn min(&self) -> Point {
+        self.min
+    }
+
+    pub fn max(&self) -> Point {
+        self.max
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    /// Whether the box has a finite size, as opposed to being empty or unbounded.
+    pub fn is_finite(&self) -> bool {
+        !self.is_empty()
+            && [
+                self.min.x, self.min.y, self.min.z, self.max.x, self.max.y, self.max.z,
+            ]
+            .iter()
+            .all(|value| value.is_finite())
+    }
+
+    pub fn including(self, point: Point) -> BoundingBox {
+        BoundingBox::new(
+            Point::at(
+                self.min.x.min(point.x),
+                self.min.y.min(point.y),
+                self.min.z.min(point.z),
+            ),
+            Point::at(
+                self.max.x.max(point.x),
+                self.max.y.max(point.y),
+                self.max.z.max(point.z),
+            ),
+        )
+    }
+
+    pub fn merge(self, other: &BoundingBox) -> BoundingBox {
+        self.including(other.min).including(other.max)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    pub fn centroid(&self) -> Point {
+        Point::at(
+            (self.min.x + self.max.x) / 2.0,
+            (self.min.y + self.max.y) / 2.0,
+            (self.min.z + self.max.z) / 2.0,
+        )
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let extent = self.max - self.min;
+        2.0 * (extent.x * extent.y + extent.y * extent.z + extent.z * extent.x)
+    }
+
+    /**
+     * The box containing this one after the transformation is applied. Empty boxes stay empty,
+     * while unbounded ones stay unbounded only along the axes their infinite extents end up on.
+     */
+    pub fn transform(&self, transformation: &Matrix) -> BoundingBox {
+        if self.is_empty() {
+            return BoundingBox::empty();
+        }
+
+        // Each axis of the result is the translation plus one term per original axis (Arvo's
+        // method), rather than transforming corners, which would mix infinities into every axis.
+        let extents = [
+            (self.min.x, self.max.x),
+            (self.min.y, self.max.y),
+            (self.min.z, self.max.z),
+        ];
+        let transformed_axis = |row: usize| {
+            let offset = transformation[row][3];
+            extents
+                .iter()
+                .enumerate()
+                .map(|(column, (low, high))| (transformation[row][column], low, high))
+                .filter(|(scale, low, high)| !ignorable(*scale, **low, **high))
+                .map(|(scale, low, high)| (scale * low, scale * high).ordered())
+                .fold((offset, offset), |(min, max), (low, high)| {
+                    (min + low, max + high)
+                })
+        };
+
+        let (min_x, max_x) = transformed_axis(0);
+        let (min_y, max_y) = transformed_axis(1);
+        let (min_z, max_z) = transformed_axis(2);
+        BoundingBox::new(
+            Point::at(min_x, min_y, min_z),
+            Point::at(max_x, max_y, max_z),
+        )
+    }
+
+    /**
+     * Whether the line along the ray passes through this box. Like Shape intersections,
+     * this considers the whole line, including anything behind the ray's origin.
+     */
+    pub fn intersects(&self, ray: &Ray) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        let (xmin, xmax) = check_axis(self.min.x, self.max.x, ray.origin().x, ray.direction().x);
+        let (ymin, ymax) = check_axis(self.min.y, self.max.y, ray.origin().y, ray.direction().y);
+        let (zmin, zmax) = check_axis(self.min.z, self.max.z, ray.origin().z, ray.direction().z);
+
+        let tmin = xmin.max(ymin).max(zmin);
+        let tmax = xmax.min(ymax).min(zmax);
+        tmin <= tmax
+    }
+}
+
+// Same approach as Cube, but for arbitrary extents.
+fn check_axis(min: f64, max: f64, origin: f64, direction: f64) -> (f64, f64) {
+    let tmin_numerator = min - origin;
+    let tmax_numerator = max - origin;
+
+    if direction.abs() >= approximate::EPSILON {
+        (tmin_numerator / direction, tmax_numerator / direction).ordered()
+    } else {
+        (tmin_numerator * f64::MAX, tmax_numerator * f64::MAX).ordered()
+    }
+}
+
+/**
+ * Whether an axis contributes nothing to another after transformation. Rotations leave tiny
+ * rounding errors in place of zeros, which would otherwise spread infinite extents around.
+ */
+fn ignorable(scale: f64, low: f64, high: f64) -> bool {
+    scale == 0.0 || (scale.abs() < approximate::EPSILON && !(low.is_finite() && high.is_finite()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::point::Point;
+    use crate::geometry::transformations::{rotation_x, rotation_y, rotation_z, translation};
+    use crate::geometry::vector::Vector;
+    use crate::tracing::bounding_box::BoundingBox;
+    use crate::tracing::ray::Ray;
+    use std::f64::consts::{PI, SQRT_2};
+
+    #[test]
+    fn adding_points_to_empty_box() {
+        let bounds = BoundingBox::empty()
+            .including(Point::at(-5, 2, 0))
+            .including(Point::at(7, 0, -3));
+
+        assert_eq!(Point::at(-5, 0, -3), bounds.min());
+        assert_eq!(Point::at(7, 2, 0), bounds.max());
+    }
+
+    #[test]
+    fn merging_boxes() {
+        let one = BoundingBox::new(Point::at(-5, -2, 0), Point::at(7, 4, 4));
+        let two = BoundingBox::new(Point::at(8, -7, -2), Point::at(14, 2, 8));
+
+        let merged = one.merge(&two);
+        assert_eq!(Point::at(-5, -7, -2), merged.min());
+        assert_eq!(Point::at(14, 4, 8), merged.max());
+    }
+
+    #[test]
+    fn box_contains_point() {
+        let bounds = BoundingBox::new(Point::at(5, -2, 0), Point::at(11, 4, 7));
+        let cases = vec![
+            (Point::at(5, -2, 0), true),
+            (Point::at(11, 4, 7), true),
+            (Point::at(8, 1, 3), true),
+            (Point::at(3, 0, 3), false),
+            (Point::at(8, -4, 3), false),
+            (Point::at(8, 1, -1), false),
+            (Point::at(13, 1, 3), false),
+            (Point::at(8, 5, 3), false),
+            (Point::at(8, 1, 8), false),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected, bounds.contains(point));
+        }
+    }
+
+    #[test]
+    fn transforming_bounding_box() {
+        let bounds = BoundingBox::new(Point::at(-1, -1, -1), Point::at(1, 1, 1));
+        let transformed = bounds.transform(&rotation_y(PI / 4.0).then(&rotation_x(PI / 4.0)));
+
+        assert_eq!(Point::at(-SQRT_2, -1.70711, -1.70711), transformed.min());
+        assert_eq!(Point::at(SQRT_2, 1.70711, 1.70711), transformed.max());
+    }
+
+    #[test]
+    fn transforming_unbounded_box_stays_unbounded() {
+        let bounds = BoundingBox::new(
+            Point::at(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Point::at(f64::INFINITY, 0.0, f64::INFINITY),
+        );
+        let transformed = bounds.transform(&translation(0, 1, 0));
+        assert!(!transformed.is_finite());
+        assert!(!transformed.is_empty());
+    }
+
+    #[test]
+    fn transforming_keeps_finite_axes_of_unbounded_box() {
+        let bounds = BoundingBox::new(
+            Point::at(-1.0, f64::NEG_INFINITY, -1.0),
+            Point::at(1.0, f64::INFINITY, 1.0),
+        );
+
+        // Point equality compares differences, which are not meaningful for infinite values.
+        let assert_at = |expected: (f64, f64, f64), actual: Point| {
+            assert_eq!(expected, (actual.x, actual.y, actual.z));
+        };
+        let moved = bounds.transform(&translation(2, 3, 4));
+        assert_at((1.0, f64::NEG_INFINITY, 3.0), moved.min());
+        assert_at((3.0, f64::INFINITY, 5.0), moved.max());
+
+        let tipped = bounds.transform(&rotation_z(PI / 2.0));
+        assert_at((f64::NEG_INFINITY, -1.0, -1.0), tipped.min());
+        assert_at((f64::INFINITY, 1.0, 1.0), tipped.max());
+    }
+
+    #[test]
+    fn surface_area_of_box() {
+        let bounds = BoundingBox::new(Point::at(0, 0, 0), Point::at(1, 2, 3));
+        assert_eq!(22.0, bounds.surface_area());
+        assert_eq!(0.0, BoundingBox::empty().surface_area());
+    }
+
+    #[test]
+    fn intersecting_ray_with_bounding_box_at_origin() {
+        let bounds = BoundingBox::new(Point::at(-1, -1, -1), Point::at(1, 1, 1));
+        let cases = vec![
+            (Point::at(5.0, 0.5, 0.0), Vector::new(-1, 0, 0), true),
+            (Point::at(-5.0, 0.5, 0.0), Vector::new(1, 0, 0), true),
+            (Point::at(0.5, 5.0, 0.0), Vector::new(0, -1, 0), true),
+            (Point::at(0.5, -5.0, 0.0), Vector::new(0, 1, 0), true),
+            (Point::at(0.5, 0.0, 5.0), Vector::new(0, 0, -1), true),
+            (Point::at(0.5, 0.0, -5.0), Vector::new(0, 0, 1), true),
+            (Point::at(0.0, 0.5, 0.0), Vector::new(0, 0, 1), true),
+            (Point::at(-2, 0, 0), Vector::new(2, 4, 6), false),
+            (Point::at(0, -2, 0), Vector::new(6, 2, 4), false),
+            (Point::at(0, 0, -2), Vector::new(4, 6, 2), false),
+            (Point::at(2, 0, 2), Vector::new(0, 0, -1), false),
+            (Point::at(0, 2, 2), Vector::new(0, -1, 0), false),
+            (Point::at(2, 2, 0), Vector::new(-1, 0, 0), false),
+        ];
+
+        for (origin, direction, expected) in cases {
+            let ray = Ray::new(origin, direction.normalize());
+            assert_eq!(expected, bounds.intersects(&ray));
+        }
+    }
+
+    #[test]
+    fn intersecting_ray_with_non_cubic_bounding_box() {
+        let bounds = BoundingBox::new(Point::at(5, -2, 0), Point::at(11, 4, 7));
+        let cases = vec![
+            (Point::at(15, 1, 2), Vector::new(-1, 0, 0), true),
+            (Point::at(-5, -1, 4), Vector::new(1, 0, 0), true),
+            (Point::at(7, 6, 5), Vector::new(0, -1, 0), true),
+            (Point::at(9, -5, 6), Vector::new(0, 1, 0), true),
+            (Point::at(8, 2, 12), Vector::new(0, 0, -1), true),
+            (Point::at(6, 0, -5), Vector::new(0, 0, 1), true),
+            (Point::at(8.0, 1.0, 3.5), Vector::new(0, 0, 1), true),
+            (Point::at(9, -1, -8), Vector::new(2, 4, 6), false),
+            (Point::at(8, 3, -4), Vector::new(6, 2, 4), false),
+            (Point::at(9, -1, -2), Vector::new(4, 6, 2), false),
+            (Point::at(4, 0, 9), Vector::new(0, 0, -1), false),
+            (Point::at(8, 6, -1), Vector::new(0, -1, 0), false),
+            (Point::at(12, 5, 4), Vector::new(-1, 0, 0), false),
+        ];
+
+        for (origin, direction, expected) in cases {
+            let ray = Ray::new(origin, direction.normalize());
+            assert_eq!(expected, bounds.intersects(&ray));
+        }
+    }
+
+    #[test]
+    fn ray_never_intersects_empty_box() {
+        let ray = Ray::new(Point::origin(), Vector::new(0, 0, 1));
+        assert!(!BoundingBox::empty().intersects(&ray));
+        assert!(BoundingBox::infinite().intersects(&ray));
+    }
+}
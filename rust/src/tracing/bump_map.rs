@@ -0,0 +1,141 @@
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::helpers::noise;
+use crate::tracing::patterns::uv_image::UvImage;
+use crate::tracing::patterns::uv_mapping::UvMapping;
+use crate::tracing::patterns::uv_pattern::UvPattern;
+
+/**
+ * Fakes small bumps on a surface by tilting the normal used for shading, without changing the
+ * geometry itself. Cheap rippled water, rough stone, etc.
+ */
+// Normal maps compare by image identity (as UvImage does), so comparing materials stays cheap.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BumpMap {
+    /// Bumps driven by noise. Scale is roughly how far apart they are, amplitude how steep.
+    Noise { scale: f64, amplitude: f64 },
+    /**
+     * Normals read from an image, mapped onto the surface. Each pixel's red, green and blue give
+     * the tilt across, up and out from the surface (in tangent space), so (0.5, 0.5, 1.0) is flat.
+     */
+    NormalMap { image: UvImage, mapping: UvMapping },
+}
+
+impl BumpMap {
+    pub fn noise(scale: f64, amplitude: f64) -> BumpMap {
+        BumpMap::Noise { scale, amplitude }
+    }
+
+    pub fn normal_map(image: UvImage, mapping: UvMapping) -> BumpMap {
+        BumpMap::NormalMap { image, mapping }
+    }
+
+    /// The (normalized) object space normal to shade with, given the point and true normal there.
+    pub fn perturb(&self, point: Point, normal: Vector) -> Vector {
+        match self {
+            BumpMap::Noise { scale, amplitude } => {
+                let scaled = Point::at(point.x / scale, point.y / scale, point.z / scale);
+                (normal + noise::perlin_vector(scaled) * *amplitude).normalize()
+            }
+            BumpMap::NormalMap { image, mapping } => {
+                let (u, v) = mapping.map(point);
+                let color = image.uv_pattern_at(u, v);
+                let (red, green, blue) = color.channels();
+
+                let (tangent, bitangent) = tangent_frame(normal);
+                let tangent_space = (red * 2.0 - 1.0, green * 2.0 - 1.0, blue * 2.0 - 1.0);
+                (tangent * tangent_space.0 + bitangent * tangent_space.1 + normal * tangent_space.2)
+                    .normalize()
+            }
+        }
+    }
+}
+
+/**
+ * Axes across and up the surface with the (normalized) normal provided. Tangents ought really
+ * to follow the texture's u and v directions, but any consistent pair looks convincing enough.
+ */
+fn tangent_frame(normal: Vector) -> (Vector, Vector) {
+    let up = if normal.y.abs() > 0.9 {
+        Vector::new(0, 0, -1)
+    } else {
+        Vector::new(0, 1, 0)
+    };
+    let tangent = up.cross(normal).normalize();
+    let bitangent = normal.cross(tangent);
+    (tangent, bitangent)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::canvas::Canvas;
+    use crate::display::color::Color;
+    use crate::display::resolution::Resolution;
+    use crate::geometry::point::Point;
+    use crate::geometry::vector::Vector;
+    use crate::helpers::approximate::Approximate;
+    use crate::tracing::bump_map::BumpMap;
+    use crate::tracing::patterns::uv_image::UvImage;
+    use crate::tracing::patterns::uv_mapping::UvMapping;
+
+    fn uniform_image(color: Color) -> UvImage {
+        let mut canvas = Canvas::new(Resolution::new(1, 1));
+        canvas.write_pixel(0, 0, color);
+        UvImage::new(canvas)
+    }
+
+    #[test]
+    fn noise_bumps_tilt_the_normal() {
+        let bump_map = BumpMap::noise(0.5, 0.3);
+        let normal = Vector::new(0, 1, 0);
+
+        let mut tilted = false;
+        for step in 0..20 {
+            let point = Point::at(step as f64 * 0.31, 0.0, step as f64 * 0.17);
+            let perturbed = bump_map.perturb(point, normal);
+            assert!(perturbed.magnitude().almost(1.0));
+            assert!(perturbed.dot(normal) > 0.5);
+            tilted |= perturbed != normal;
+        }
+        assert!(tilted);
+    }
+
+    #[test]
+    fn flat_bumps_leave_the_normal_alone() {
+        let normal = Vector::new(1, 1, 0).normalize();
+        let point = Point::at(0.3, 0.4, 0.5);
+
+        assert_eq!(normal, BumpMap::noise(1.0, 0.0).perturb(point, normal));
+        let flat = BumpMap::normal_map(uniform_image(Color::new(0.5, 0.5, 1.0)), UvMapping::Planar);
+        assert_eq!(normal, flat.perturb(point, normal));
+    }
+
+    #[test]
+    fn normal_map_tilts_across_and_up_the_surface() {
+        let normal = Vector::new(0, 0, -1);
+        let point = Point::origin();
+
+        let across =
+            BumpMap::normal_map(uniform_image(Color::new(1.0, 0.5, 0.5)), UvMapping::Planar);
+        let up = BumpMap::normal_map(uniform_image(Color::new(0.5, 1.0, 0.5)), UvMapping::Planar);
+
+        let across = across.perturb(point, normal);
+        let up = up.perturb(point, normal);
+        assert!(across.dot(normal).almost(0.0));
+        assert!(up.dot(normal).almost(0.0));
+        assert!(across.dot(up).almost(0.0));
+        assert_eq!(Vector::new(0, 1, 0), up);
+    }
+
+    #[test]
+    fn normal_maps_are_equal_only_to_copies_of_themselves() {
+        let flat = Color::new(0.5, 0.5, 1.0);
+        let normal_map = BumpMap::normal_map(uniform_image(flat), UvMapping::Planar);
+
+        assert_eq!(normal_map, normal_map.clone());
+        assert_ne!(
+            normal_map,
+            BumpMap::normal_map(uniform_image(flat), UvMapping::Planar)
+        );
+    }
+}
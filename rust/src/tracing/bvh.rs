@@ -0,0 +1,313 @@
+use crate::geometry::point::Point;
+use crate::tracing::bounding_box::BoundingBox;
+use crate::tracing::intersection::Intersection;
+use crate::tracing::ray::Ray;
+use crate::tracing::shapes::shape::Shape;
+
+// Nodes with this few shapes are not worth splitting further.
+const MAX_LEAF_SIZE: usize = 2;
+
+// The cost of testing a ray against a box, relative to testing it against a shape.
+const TRAVERSAL_COST: f64 = 0.125;
+
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf {
+        bounds: BoundingBox,
+        shapes: Vec<usize>,
+    },
+    Branch {
+        bounds: BoundingBox,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl Node {
+    fn bounds(&self) -> &BoundingBox {
+        match self {
+            Node::Leaf { bounds, .. } => bounds,
+            Node::Branch { bounds, .. } => bounds,
+        }
+    }
+}
+
+// A shape being sorted into the hierarchy.
+#[derive(Debug, Clone)]
+struct Entry {
+    index: usize,
+    bounds: BoundingBox,
+    centroid: Point,
+}
+
+/**
+ * Bounding volume hierarchy over a slice of Shapes, built using the surface area heuristic.
+ *
+ * Only stores indexes, so must be used with the same slice it was built from. Shapes with
+ * unbounded extent (e.g. planes) are kept to one side and tested against every ray.
+ */
+#[derive(Debug, Clone)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    root: Option<usize>,
+    unbounded: Vec<usize>,
+}
+
+impl Bvh {
+    pub fn build(shapes: &[Shape]) -> Bvh {
+        let mut entries = Vec::new();
+        let mut unbounded = Vec::new();
+
+        for (index, shape) in shapes.iter().enumerate() {
+            let bounds = shape.bounds();
+            if bounds.is_empty() {
+                // Nothing there to hit
+                continue;
+            }
+            if bounds.is_finite() {
+                entries.push(Entry {
+                    index,
+                    bounds,
+                    centroid: bounds.centroid(),
+                });
+            } else {
+                unbounded.push(index);
+            }
+        }
+
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            root: None,
+            unbounded,
+        };
+        if !entries.is_empty() {
+            bvh.root = Some(bvh.build_node(entries));
+        }
+        bvh
+    }
+
+    /// The bounds of everything in the hierarchy, or an infinite box if anything is unbounded.
+    pub fn bounds(&self) -> BoundingBox {
+        if !self.unbounded.is_empty() {
+            return BoundingBox::infinite();
+        }
+        self.root
+            .map_or(BoundingBox::empty(), |root| *self.nodes[root].bounds())
+    }
+
+    /// Intersect the ray with those shapes whose bounds it passes through.
+    pub fn intersect<'a>(&self, shapes: &'a [Shape], ray: &Ray) -> Vec<Intersection<'a>> {
+        let mut intersections: Vec<Intersection> = self
+            .unbounded
+            .iter()
+            .flat_map(|index| shapes[*index].intersect(ray).into_iter())
+            .collect();
+
+        let mut pending: Vec<usize> = self.root.into_iter().collect();
+        while let Some(node_index) = pending.pop() {
+            let node = &self.nodes[node_index];
+            if !node.bounds().intersects(ray) {
+                continue;
+            }
+
+            match node {
+                Node::Leaf { shapes: leaf, .. } => intersections.extend(
+                    leaf.iter()
+                        .flat_map(|index| shapes[*index].intersect(ray).into_iter()),
+                ),
+                Node::Branch { left, right, .. } => {
+                    pending.push(*left);
+                    pending.push(*right);
+                }
+            }
+        }
+
+        intersections
+    }
+
+    fn build_node(&mut self, mut entries: Vec<Entry>) -> usize {
+        let bounds = entries.iter().fold(BoundingBox::empty(), |bounds, entry| {
+            bounds.merge(&entry.bounds)
+        });
+
+        let split = if entries.len() <= MAX_LEAF_SIZE {
+            None
+        } else {
+            best_split(&mut entries, &bounds)
+        };
+
+        let node = match split {
+            None => Node::Leaf {
+                bounds,
+                shapes: entries.iter().map(|entry| entry.index).collect(),
+            },
+            Some((axis, split_index)) => {
+                sort_by_axis(&mut entries, axis);
+                let right_entries = entries.split_off(split_index);
+                let left = self.build_node(entries);
+                let right = self.build_node(right_entries);
+                Node::Branch {
+                    bounds,
+                    left,
+                    right,
+                }
+            }
+        };
+
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+}
+
+/**
+ * Find the axis and position to split the entries at, minimizing the surface area heuristic.
+ * Returns None if keeping the entries together in a leaf is cheaper than any split.
+ */
+fn best_split(entries: &mut [Entry], bounds: &BoundingBox) -> Option<(usize, usize)> {
+    let count = entries.len();
+    let parent_area = bounds.surface_area();
+    if parent_area <= 0.0 {
+        // Everything is piled into a single point, so no split can help.
+        return None;
+    }
+
+    let leaf_cost = count as f64;
+    let mut best: Option<(f64, usize, usize)> = None;
+
+    for axis in 0..3 {
+        sort_by_axis(entries, axis);
+
+        // left_areas[i] is the area of the first i entries, right_areas[i] of the remainder.
+        let mut left_areas = vec![0.0; count + 1];
+        let mut running = BoundingBox::empty();
+        for (index, entry) in entries.iter().enumerate() {
+            running = running.merge(&entry.bounds);
+            left_areas[index + 1] = running.surface_area();
+        }
+
+        let mut right_areas = vec![0.0; count + 1];
+        let mut running = BoundingBox::empty();
+        for (index, entry) in entries.iter().enumerate().rev() {
+            running = running.merge(&entry.bounds);
+            right_areas[index] = running.surface_area();
+        }
+
+        for split_index in 1..count {
+            let cost = TRAVERSAL_COST
+                + (left_areas[split_index] * split_index as f64
+                    + right_areas[split_index] * (count - split_index) as f64)
+                    / parent_area;
+            if !matches!(best, Some((best_cost, _, _)) if best_cost <= cost) {
+                best = Some((cost, axis, split_index));
+            }
+        }
+    }
+
+    best.filter(|(cost, _, _)| *cost < leaf_cost)
+        .map(|(_, axis, split_index)| (axis, split_index))
+}
+
+fn sort_by_axis(entries: &mut [Entry], axis: usize) {
+    entries.sort_by(|a, b| {
+        axis_value(a.centroid, axis)
+            .partial_cmp(&axis_value(b.centroid, axis))
+            .unwrap()
+    });
+}
+
+fn axis_value(point: Point, axis: usize) -> f64 {
+    match axis {
+        0 => point.x,
+        1 => point.y,
+        _ => point.z,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::point::Point;
+    use crate::geometry::transformations::{scaling, translation};
+    use crate::geometry::vector::Vector;
+    use crate::tracing::bvh::{Bvh, Node};
+    use crate::tracing::intersection::Intersections;
+    use crate::tracing::ray::Ray;
+    use crate::tracing::shapes::shape::Shape;
+    use rand::prelude::*;
+
+    #[test]
+    fn bvh_of_nothing_has_no_intersections() {
+        let bvh = Bvh::build(&[]);
+        let ray = Ray::new(Point::origin(), Vector::new(0, 0, 1));
+        assert!(bvh.intersect(&[], &ray).is_empty());
+    }
+
+    #[test]
+    fn distant_clusters_are_split_apart() {
+        let shapes: Vec<Shape> = vec![-10, -9, -8, 8, 9, 10]
+            .into_iter()
+            .map(|x| Shape::sphere().with_transform(translation(x, 0, 0)))
+            .collect();
+        let bvh = Bvh::build(&shapes);
+
+        match &bvh.nodes[bvh.root.unwrap()] {
+            Node::Branch { left, right, .. } => {
+                let left_bounds = bvh.nodes[*left].bounds();
+                let right_bounds = bvh.nodes[*right].bounds();
+                assert_eq!(Point::at(-11, -1, -1), left_bounds.min());
+                assert_eq!(Point::at(-7, 1, 1), left_bounds.max());
+                assert_eq!(Point::at(7, -1, -1), right_bounds.min());
+                assert_eq!(Point::at(11, 1, 1), right_bounds.max());
+            }
+            Node::Leaf { .. } => panic!("expected the root to be split"),
+        }
+    }
+
+    #[test]
+    fn unbounded_shapes_are_always_tested() {
+        let shapes = vec![
+            Shape::plane(),
+            Shape::sphere().with_transform(translation(10, 0, 0)),
+        ];
+        let bvh = Bvh::build(&shapes);
+        let ray = Ray::new(Point::at(0, 5, 0), Vector::new(0, -1, 0));
+
+        let intersections = bvh.intersect(&shapes, &ray);
+        assert_eq!(1, intersections.len());
+        assert_eq!(&shapes[0], intersections[0].thing());
+    }
+
+    #[test]
+    fn bvh_finds_same_intersections_as_testing_everything() {
+        let mut rng = StdRng::seed_from_u64(1234);
+        let shapes: Vec<Shape> = (0..200)
+            .map(|_| {
+                let radius = rng.gen_range(0.1..0.5);
+                Shape::sphere().with_transform(scaling(radius, radius, radius).then(&translation(
+                    rng.gen_range(-5.0..5.0),
+                    rng.gen_range(-5.0..5.0),
+                    rng.gen_range(-5.0..5.0),
+                )))
+            })
+            .collect();
+        let bvh = Bvh::build(&shapes);
+
+        for _ in 0..100 {
+            let origin = Point::at(
+                rng.gen_range(-10.0..10.0),
+                rng.gen_range(-10.0..10.0),
+                -10.0,
+            );
+            let direction = Vector::new(rng.gen_range(-0.5..0.5), rng.gen_range(-0.5..0.5), 1.0);
+            let ray = Ray::new(origin, direction.normalize());
+
+            let expected = Intersections::new(
+                shapes
+                    .iter()
+                    .flat_map(|shape| shape.intersect(&ray).into_iter())
+                    .collect(),
+            );
+            let actual = Intersections::new(bvh.intersect(&shapes, &ray));
+            assert_eq!(expected, actual);
+        }
+    }
+}
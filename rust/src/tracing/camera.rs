@@ -2,10 +2,17 @@ use crate::display::canvas::Canvas;
 use crate::display::color::Color;
 use crate::display::resolution::Resolution;
 use crate::geometry::matrix::Matrix;
-use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::helpers::general;
+use crate::tracing::integrator::Integrator;
+use crate::tracing::projections::perspective::Perspective;
+use crate::tracing::projections::projection::Projection;
 use crate::tracing::ray::Ray;
+use crate::tracing::sampling::PixelSampling;
 use crate::tracing::world::World;
+use rand::prelude::*;
 use rayon::prelude::*;
+use std::f64::consts::PI;
 
 /// Type mapping a resolution onto a Camera.
 /// Allows doing most of the setup for a camera without specifying the resolution.
@@ -14,8 +21,13 @@ pub type CameraMaker = Box<dyn FnOnce(Resolution) -> Camera>;
 pub struct Camera {
     resolution: Resolution,
     transform: Matrix,
-    half_height: f64,
-    half_width: f64,
+    projection: Box<dyn Projection>,
+    sampling: PixelSampling,
+    integrator: Integrator,
+    // Radius of the lens. Zero gives a pinhole camera, with everything in focus.
+    aperture: f64,
+    // Distance from the camera at which things are perfectly in focus.
+    focal_distance: f64,
 }
 
 impl Camera {
@@ -23,22 +35,77 @@ impl Camera {
         Camera {
             resolution,
             transform,
-            half_height: Camera::calculate_half_height(resolution, field_of_view),
-            half_width: Camera::calculate_half_width(resolution, field_of_view),
+            projection: Box::new(Perspective::new(field_of_view)),
+            sampling: PixelSampling::Center,
+            integrator: Integrator::Whitted,
+            aperture: 0.0,
+            focal_distance: 1.0,
         }
     }
 
+    /**
+     * Use a lens of the radius provided, focused at focal_distance. Things nearer or further
+     * away are blurred, more so with a larger aperture, so plenty of samples per pixel are needed.
+     */
+    pub fn with_lens(self, aperture: f64, focal_distance: f64) -> Camera {
+        Camera {
+            aperture,
+            focal_distance,
+            ..self
+        }
+    }
+
+    /// Map the image onto the world with the projection provided, rather than the usual perspective.
+    pub fn with_projection<P: Projection + 'static>(self, projection: P) -> Camera {
+        Camera {
+            projection: Box::new(projection),
+            ..self
+        }
+    }
+
+    /// Fire several rays per pixel and average them, smoothing jagged edges.
+    pub fn with_sampling(self, sampling: PixelSampling) -> Camera {
+        Camera { sampling, ..self }
+    }
+
+    /// Work out the color seen along each ray with the integrator provided (Whitted by default).
+    pub fn with_integrator(self, integrator: Integrator) -> Camera {
+        Camera { integrator, ..self }
+    }
+
     /**
      * Produce the image of the world as seen from this camera.
      */
     pub(crate) fn render(&self, world: World) -> Canvas {
+        let canvas = self.render_pixels(|x, y| self.color_for_pixel(&world, x, y));
+
+        match self.sampling {
+            // Only re-render those pixels that differ from a neighbor by more than threshold.
+            PixelSampling::Adaptive {
+                threshold,
+                max_depth,
+            } => self.render_pixels(|x, y| {
+                if self.differs_from_neighbors(&canvas, x, y, threshold) {
+                    self.subdivide(&world, x, y, (0.0, 0.0, 1.0), threshold, max_depth)
+                } else {
+                    canvas.pixel_at(x, y)
+                }
+            }),
+            _ => canvas,
+        }
+    }
+
+    /// Produce a canvas by (in parallel) coloring each pixel with the function provided.
+    fn render_pixels<F>(&self, color_of: F) -> Canvas
+    where
+        F: Fn(usize, usize) -> Color + Sync,
+    {
         let mut canvas = Canvas::new(self.resolution);
         let pixels: Vec<(usize, usize, Color)> = self
             .resolution
             .coordinates()
             .par_iter()
-            .map(|(x, y)| (*x, *y, self.ray_for_pixel(*x, *y)))
-            .map(|(x, y, ray)| (x, y, world.color_at(&ray)))
+            .map(|(x, y)| (*x, *y, color_of(*x, *y)))
             .collect();
         pixels
             .into_iter()
@@ -47,48 +114,152 @@ impl Camera {
         canvas
     }
 
-    fn ray_for_pixel(&self, x: usize, y: usize) -> Ray {
-        // the offset from the edge of the canvas to the pixel's center
-        let x_offset = (x as f64 + 0.5) * self.pixel_size();
-        let y_offset = (y as f64 + 0.5) * self.pixel_size();
+    fn differs_from_neighbors(&self, canvas: &Canvas, x: usize, y: usize, threshold: f64) -> bool {
+        let color = canvas.pixel_at(x, y);
+        let x_range = x.saturating_sub(1)..(x + 2).min(self.resolution.hsize());
+        let y_range = y.saturating_sub(1)..(y + 2).min(self.resolution.vsize());
 
-        // the untransformed coordinates of the pixel in world space
-        // (remember that the camera looks toward -z, so +x is to the left)
-        let world_x = self.half_width - x_offset;
-        let world_y = self.half_height - y_offset;
+        general::enumerate_coordinates(x_range, y_range)
+            .into_iter()
+            .any(|(nx, ny)| canvas.pixel_at(nx, ny).difference(&color) > threshold)
+    }
+
+    /**
+     * The color of the square (left, top, size) within the pixel, as fractions of the pixel.
+     * Averages the square's corners, unless they differ by more than threshold, in which case
+     * each quarter of the square is colored in the same way (until out of depth).
+     */
+    fn subdivide(
+        &self,
+        world: &World,
+        x: usize,
+        y: usize,
+        (left, top, size): (f64, f64, f64),
+        threshold: f64,
+        depth_remaining: usize,
+    ) -> Color {
+        let corners: Vec<Color> = [
+            (left, top),
+            (left + size, top),
+            (left, top + size),
+            (left + size, top + size),
+        ]
+        .iter()
+        .map(|(x_offset, y_offset)| self.color_through_pixel(world, x, y, *x_offset, *y_offset))
+        .collect();
+
+        let differs = corners
+            .iter()
+            .any(|corner| corner.difference(&corners[0]) > threshold);
+        if !differs || depth_remaining == 0 {
+            return corners
+                .into_iter()
+                .fold(Color::BLACK, |total, color| total + color)
+                * 0.25;
+        }
 
-        // using the camera matrix, transform the canvas point and the origin
-        // and then compute the ray's direction vector
-        // remember that the canvas is at z=-1
-        let pixel = &self.transform.inverse() * Point::at(world_x, world_y, -1.0);
-        let origin = &self.transform.inverse() * Point::origin();
-        let direction = (pixel - origin).normalize();
+        let half = size / 2.0;
+        [
+            (left, top),
+            (left + half, top),
+            (left, top + half),
+            (left + half, top + half),
+        ]
+        .iter()
+        .map(|(quarter_left, quarter_top)| {
+            self.subdivide(
+                world,
+                x,
+                y,
+                (*quarter_left, *quarter_top, half),
+                threshold,
+                depth_remaining - 1,
+            )
+        })
+        .fold(Color::BLACK, |total, color| total + color)
+            * 0.25
+    }
 
-        Ray::new(origin, direction)
+    /// The average color seen by each of the rays sampling the pixel.
+    fn color_for_pixel(&self, world: &World, x: usize, y: usize) -> Color {
+        let offsets = self.sampling.offsets();
+        let total = offsets
+            .iter()
+            .map(|(x_offset, y_offset)| self.color_through_pixel(world, x, y, *x_offset, *y_offset))
+            .fold(Color::BLACK, |total, color| total + color);
+        total * (1.0 / offsets.len() as f64)
     }
 
-    fn pixel_size(&self) -> f64 {
-        self.half_width * 2.0 / self.resolution.hsize() as f64
+    /// The color seen through the pixel at the offsets provided (black where the projection sees nothing).
+    fn color_through_pixel(
+        &self,
+        world: &World,
+        x: usize,
+        y: usize,
+        x_fraction: f64,
+        y_fraction: f64,
+    ) -> Color {
+        self.ray_through_pixel(x, y, x_fraction, y_fraction)
+            .map_or(Color::BLACK, |ray| self.integrator.color_at(world, &ray))
     }
 
-    fn calculate_half_height(resolution: Resolution, field_of_view: f64) -> f64 {
-        let half_view = (field_of_view / 2.0).tan();
+    fn ray_for_pixel(&self, x: usize, y: usize) -> Option<Ray> {
+        self.ray_through_pixel(x, y, 0.5, 0.5)
+    }
 
-        if resolution.aspect() >= 1.0 {
-            half_view / resolution.aspect()
+    /// The ray through the pixel at the offsets (as fractions of the pixel size) provided.
+    fn ray_through_pixel(
+        &self,
+        x: usize,
+        y: usize,
+        x_fraction: f64,
+        y_fraction: f64,
+    ) -> Option<Ray> {
+        let ray = self.projection.ray_for(
+            self.resolution,
+            x as f64 + x_fraction,
+            y as f64 + y_fraction,
+        )?;
+
+        let ray = if self.aperture > 0.0 {
+            let (lens_x, lens_y) = self.sample_lens();
+            self.through_lens(&ray, lens_x, lens_y)
         } else {
-            half_view
-        }
+            ray
+        };
+
+        // using the camera matrix, move the ray from camera space into the world
+        let world_ray = ray.transform(self.transform.inverse());
+        Some(Ray::new(
+            world_ray.origin(),
+            world_ray.direction().normalize(),
+        ))
     }
 
-    fn calculate_half_width(resolution: Resolution, field_of_view: f64) -> f64 {
-        let half_view = (field_of_view / 2.0).tan();
-
-        if resolution.aspect() >= 1.0 {
-            half_view
+    /**
+     * The (camera space) ray from the point provided on the lens toward where the pinhole ray
+     * crosses the focal plane. Rays from anywhere on the lens meet there, so only things
+     * at the focal distance are sharp.
+     */
+    fn through_lens(&self, ray: &Ray, lens_x: f64, lens_y: f64) -> Ray {
+        // Rays looking away from the focal plane (e.g. in a 360° view) focus at that distance instead.
+        let time = if ray.direction().z < 0.0 {
+            self.focal_distance / -ray.direction().z
         } else {
-            half_view * resolution.aspect()
-        }
+            self.focal_distance
+        };
+        let focus = ray.position(time);
+
+        let origin = ray.origin() + Vector::new(lens_x, lens_y, 0.0);
+        Ray::new(origin, (focus - origin).normalize())
+    }
+
+    /// A random point on the lens, uniformly distributed over its area.
+    fn sample_lens(&self) -> (f64, f64) {
+        let mut rng = thread_rng();
+        let radius = self.aperture * rng.gen_range(0.0..1.0_f64).sqrt();
+        let angle = rng.gen_range(0.0..2.0 * PI);
+        (radius * angle.cos(), radius * angle.sin())
     }
 }
 
@@ -100,8 +271,13 @@ mod tests {
     use crate::geometry::point::Point;
     use crate::geometry::transformations::{rotation_y, translation, view_transform};
     use crate::geometry::vector::Vector;
-    use crate::helpers::approximate::Approximate;
+    use crate::tracing::ambient_occlusion::AmbientOcclusion;
     use crate::tracing::camera::Camera;
+    use crate::tracing::integrator::Integrator;
+    use crate::tracing::projections::fisheye::Fisheye;
+    use crate::tracing::projections::orthographic::Orthographic;
+    use crate::tracing::ray::Ray;
+    use crate::tracing::sampling::PixelSampling;
     use crate::tracing::world::World;
     use std::f64::consts::PI;
 
@@ -116,27 +292,11 @@ mod tests {
         assert_eq!(Matrix::identity(4), camera.transform);
     }
 
-    #[test]
-    fn pixel_size_for_horizontal_canvas() {
-        let camera = Camera::new(Resolution::new(200, 125), PI / 2.0, Matrix::identity(4));
-        let a = 0.01;
-        let b = camera.pixel_size();
-        assert!(a.almost(b));
-    }
-
-    #[test]
-    fn pixel_size_for_vertical_canvas() {
-        let camera = Camera::new(Resolution::new(125, 200), PI / 2.0, Matrix::identity(4));
-        let a = 0.01;
-        let b = camera.pixel_size();
-        assert!(a.almost(b));
-    }
-
     #[test]
     fn constructing_ray_through_center_of_the_canvas() {
         let camera = Camera::new(Resolution::new(201, 101), PI / 2.0, Matrix::identity(4));
 
-        let ray = camera.ray_for_pixel(100, 50);
+        let ray = camera.ray_for_pixel(100, 50).unwrap();
 
         assert_eq!(Point::origin(), ray.position(0));
         assert_eq!(Vector::new(0, 0, -1), ray.direction());
@@ -146,7 +306,7 @@ mod tests {
     fn constructing_ray_through_corner_of_canvas() {
         let camera = Camera::new(Resolution::new(201, 101), PI / 2.0, Matrix::identity(4));
 
-        let ray = camera.ray_for_pixel(0, 0);
+        let ray = camera.ray_for_pixel(0, 0).unwrap();
 
         assert_eq!(Point::origin(), ray.position(0));
         assert_eq!(Vector::new(0.66519, 0.33259, -0.66851), ray.direction());
@@ -157,7 +317,7 @@ mod tests {
         let transform = translation(0, -2, 5).then(&rotation_y(PI / 4.0));
         let camera = Camera::new(Resolution::new(201, 101), PI / 2.0, transform);
 
-        let ray = camera.ray_for_pixel(100, 50);
+        let ray = camera.ray_for_pixel(100, 50).unwrap();
 
         assert_eq!(Point::at(0, 2, -5), ray.position(0));
         assert_eq!(
@@ -182,4 +342,134 @@ mod tests {
         let image = camera.render(world);
         assert_eq!(Color::new(0.38066, 0.47583, 0.2855), image.pixel_at(5, 5));
     }
+
+    #[test]
+    fn rendering_with_another_integrator() {
+        let camera = Camera::new(
+            Resolution::new(11, 11),
+            PI / 2.0,
+            view_transform(Point::at(0, 0, -5), Point::origin(), Vector::new(0, 1, 0)),
+        )
+        .with_integrator(Integrator::AmbientOcclusion(AmbientOcclusion::new(8, 1.0)));
+
+        // Nothing is near enough the front of the outer sphere to hide any of it.
+        let image = camera.render(World::default());
+        assert_eq!(Color::WHITE, image.pixel_at(5, 5));
+    }
+
+    #[test]
+    fn supersampling_averages_rays_across_the_pixel() {
+        let world = World::default();
+        let transform = view_transform(Point::at(0, 0, -5), Point::origin(), Vector::new(0, 1, 0));
+        let camera = Camera::new(Resolution::new(11, 11), PI / 2.0, transform)
+            .with_sampling(PixelSampling::Grid(2));
+
+        // A pixel on the edge of the sphere, partly covering the background.
+        let (x, y) = (4, 5);
+        let expected = [(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)]
+            .iter()
+            .map(|(x_offset, y_offset)| {
+                camera.color_through_pixel(&world, x, y, *x_offset, *y_offset)
+            })
+            .fold(Color::BLACK, |total, color| total + color)
+            * 0.25;
+
+        let color = camera.color_for_pixel(&world, x, y);
+        assert_eq!(expected, color);
+        assert_ne!(world.color_at(&camera.ray_for_pixel(x, y).unwrap()), color);
+    }
+
+    #[test]
+    fn adaptive_sampling_only_refines_pixels_near_edges() {
+        let transform = view_transform(Point::at(0, 0, -5), Point::origin(), Vector::new(0, 1, 0));
+        let camera = Camera::new(Resolution::new(11, 11), PI / 2.0, transform.clone());
+        let adaptive = Camera::new(Resolution::new(11, 11), PI / 2.0, transform).with_sampling(
+            PixelSampling::Adaptive {
+                threshold: 0.1,
+                max_depth: 2,
+            },
+        );
+
+        let plain_image = camera.render(World::default());
+        let adaptive_image = adaptive.render(World::default());
+
+        // The background far from the sphere is left alone...
+        assert_eq!(plain_image.pixel_at(0, 0), adaptive_image.pixel_at(0, 0));
+        // ...while the sphere's edge is blended with the background.
+        assert_ne!(plain_image.pixel_at(4, 5), adaptive_image.pixel_at(4, 5));
+    }
+
+    #[test]
+    fn subdividing_uniform_region_averages_its_corners() {
+        let world = World::default();
+        let transform = view_transform(Point::at(0, 0, -5), Point::origin(), Vector::new(0, 1, 0));
+        let camera = Camera::new(Resolution::new(11, 11), PI / 2.0, transform);
+
+        // Entirely background, so no further subdivision is needed.
+        let color = camera.subdivide(&world, 0, 0, (0.0, 0.0, 1.0), 0.1, 4);
+        assert_eq!(Color::BLACK, color);
+    }
+
+    #[test]
+    fn zero_aperture_is_a_pinhole() {
+        let transform = translation(0, -2, 5).then(&rotation_y(PI / 4.0));
+        let pinhole = Camera::new(Resolution::new(201, 101), PI / 2.0, transform.clone());
+        let lens = Camera::new(Resolution::new(201, 101), PI / 2.0, transform).with_lens(0.0, 5.0);
+
+        assert_eq!(pinhole.ray_for_pixel(30, 70), lens.ray_for_pixel(30, 70));
+    }
+
+    #[test]
+    fn rays_through_lens_meet_at_focal_distance() {
+        let camera = Camera::new(Resolution::new(201, 101), PI / 2.0, Matrix::identity(4))
+            .with_lens(0.5, 4.0);
+
+        let pinhole = Ray::new(Point::origin(), Vector::new(0.25, -0.1, -1.0).normalize());
+        let through_center = camera.through_lens(&pinhole, 0.0, 0.0);
+        let through_edge = camera.through_lens(&pinhole, 0.3, -0.4);
+
+        assert_eq!(Point::origin(), through_center.position(0));
+        assert_eq!(Point::at(0.3, -0.4, 0.0), through_edge.position(0));
+
+        let focus = Point::at(1.0, -0.4, -4.0);
+        let to_focus = |ray: &Ray| ray.position((focus - ray.position(0)).magnitude());
+        assert_eq!(focus, to_focus(&through_center));
+        assert_eq!(focus, to_focus(&through_edge));
+    }
+
+    #[test]
+    fn lens_samples_lie_within_aperture() {
+        let camera = Camera::new(Resolution::new(10, 10), PI / 2.0, Matrix::identity(4))
+            .with_lens(0.25, 3.0);
+
+        for _ in 0..100 {
+            let ray = camera.ray_for_pixel(5, 5).unwrap();
+            let origin = ray.position(0);
+            assert!((origin - Point::origin()).magnitude() <= 0.25);
+            assert_eq!(0.0, origin.z);
+        }
+    }
+
+    #[test]
+    fn camera_uses_projection_provided() {
+        let transform = translation(0, -2, 5);
+        let camera = Camera::new(Resolution::new(200, 100), PI / 2.0, transform)
+            .with_projection(Orthographic::new(4.0));
+
+        let ray = camera.ray_for_pixel(0, 0).unwrap();
+        assert_eq!(Point::at(1.99, 2.99, -5.0), ray.position(0));
+        assert_eq!(Vector::new(0, 0, -1), ray.direction());
+    }
+
+    #[test]
+    fn pixels_outside_projection_are_black() {
+        let camera = Camera::new(Resolution::new(20, 10), PI / 2.0, Matrix::identity(4))
+            .with_projection(Fisheye::new(PI));
+
+        assert!(camera.ray_for_pixel(0, 0).is_none());
+        assert_eq!(
+            Color::BLACK,
+            camera.color_for_pixel(&World::default(), 0, 0)
+        );
+    }
 }
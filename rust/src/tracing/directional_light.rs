@@ -0,0 +1,56 @@
+use crate::display::color::Color;
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::tracing::light::{Light, LightSample};
+
+/**
+ * A light infinitely far away, such as the sun. It shines in the same direction everywhere,
+ * with no falloff, so shadows are cast by anything between a point and the sky.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectionalLight {
+    // The direction the light travels, i.e. away from its source.
+    direction: Vector,
+    intensity: Color,
+}
+
+impl DirectionalLight {
+    pub fn new(direction: Vector, intensity: Color) -> DirectionalLight {
+        DirectionalLight {
+            direction: direction.normalize(),
+            intensity,
+        }
+    }
+}
+
+impl Light for DirectionalLight {
+    fn intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn samples_from(&self, _: Point) -> Vec<LightSample> {
+        vec![LightSample::new(-self.direction, f64::INFINITY)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::geometry::point::Point;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::directional_light::DirectionalLight;
+    use crate::tracing::light::Light;
+
+    #[test]
+    fn directional_light_comes_from_the_same_direction_everywhere() {
+        let light = DirectionalLight::new(Vector::new(0, -2, 0), Color::WHITE);
+
+        for point in [Point::origin(), Point::at(100, -50, 3)] {
+            let samples = light.samples_from(point);
+            assert_eq!(1, samples.len());
+            assert_eq!(Vector::new(0, 1, 0), samples[0].direction());
+            assert_eq!(f64::INFINITY, samples[0].distance());
+            assert_eq!(1.0, light.falloff_at(point));
+        }
+    }
+}
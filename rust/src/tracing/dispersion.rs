@@ -0,0 +1,53 @@
+/**
+ * The wavelengths (in micrometres) standing in for each color channel when dispersion splits
+ * light up, with glass bending blue light more than red.
+ */
+pub const RED_WAVELENGTH: f64 = 0.65;
+pub const GREEN_WAVELENGTH: f64 = 0.55;
+pub const BLUE_WAVELENGTH: f64 = 0.45;
+
+// The Fraunhofer lines that refractive indexes and Abbe numbers are conventionally quoted at.
+const D_LINE: f64 = 0.5876;
+const F_LINE: f64 = 0.4861;
+const C_LINE: f64 = 0.6563;
+
+/**
+ * The refractive index at wavelength, of a material with the refractive_index (at the yellow
+ * D line) and Abbe number provided, using Cauchy's equation: n = a + b / wavelength^2.
+ * Lower Abbe numbers disperse more, e.g. around 60 for crown glass, 30 for flint glass.
+ */
+pub fn refractive_index_at(refractive_index: f64, abbe_number: f64, wavelength: f64) -> f64 {
+    // The Abbe number is (n_D - 1) / (n_F - n_C), which fixes b; then a must give n_D.
+    let b =
+        (refractive_index - 1.0) / (abbe_number * (1.0 / F_LINE.powi(2) - 1.0 / C_LINE.powi(2)));
+    let a = refractive_index - b / D_LINE.powi(2);
+    a + b / wavelength.powi(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::approximate::Approximate;
+    use crate::tracing::dispersion::{
+        refractive_index_at, BLUE_WAVELENGTH, C_LINE, D_LINE, F_LINE, RED_WAVELENGTH,
+    };
+
+    #[test]
+    fn refractive_index_is_as_given_at_d_line() {
+        assert!(refractive_index_at(1.5, 40.0, D_LINE).almost(1.5));
+    }
+
+    #[test]
+    fn abbe_number_sets_spread_of_indexes() {
+        let spread =
+            refractive_index_at(1.5, 40.0, F_LINE) - refractive_index_at(1.5, 40.0, C_LINE);
+        assert!(spread.almost(0.5 / 40.0));
+    }
+
+    #[test]
+    fn blue_light_bends_more_than_red() {
+        let red = refractive_index_at(1.5, 40.0, RED_WAVELENGTH);
+        let blue = refractive_index_at(1.5, 40.0, BLUE_WAVELENGTH);
+        assert!(blue > 1.5);
+        assert!(red < 1.5);
+    }
+}
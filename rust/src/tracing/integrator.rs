@@ -0,0 +1,90 @@
+use crate::display::color::Color;
+use crate::tracing::ambient_occlusion::AmbientOcclusion;
+use crate::tracing::ray::Ray;
+use crate::tracing::world::World;
+
+/// How Camera works out the color seen along each ray it fires into the World.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Integrator {
+    /**
+     * Classic (Whitted) ray tracing: Phong lighting from each light, plus mirror reflection
+     * and refraction. Fast and noise free, but with a flat ambient term in place of indirect light.
+     */
+    Whitted,
+    /**
+     * Monte Carlo path tracing, averaging samples paths per ray. Light bounces between surfaces,
+     * giving color bleeding and soft indirect lighting, at the cost of noise unless samples is large.
+     */
+    PathTraced { samples: usize },
+    /// Not lighting at all, but a grayscale image of the ambient occlusion of each hit.
+    AmbientOcclusion(AmbientOcclusion),
+}
+
+impl Integrator {
+    pub fn color_at(&self, world: &World, ray: &Ray) -> Color {
+        match *self {
+            Integrator::Whitted => world.color_at(ray),
+            Integrator::PathTraced { samples } => {
+                // Every ray needs at least one path, or its color would be 0 / 0.
+                let samples = samples.max(1);
+                let total = (0..samples)
+                    .map(|_| world.path_traced_color_at(ray))
+                    .fold(Color::BLACK, |total, color| total + color);
+                total * (1.0 / samples as f64)
+            }
+            Integrator::AmbientOcclusion(ambient_occlusion) => {
+                world.ambient_occlusion_at(ray, &ambient_occlusion)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::geometry::point::Point;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::ambient_occlusion::AmbientOcclusion;
+    use crate::tracing::integrator::Integrator;
+    use crate::tracing::ray::Ray;
+    use crate::tracing::world::World;
+
+    #[test]
+    fn whitted_integrator_is_the_usual_ray_tracing() {
+        let world = World::default();
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+        assert_eq!(
+            world.color_at(&ray),
+            Integrator::Whitted.color_at(&world, &ray)
+        );
+    }
+
+    #[test]
+    fn path_traced_misses_are_black() {
+        let world = World::default();
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 1, 0));
+        let integrator = Integrator::PathTraced { samples: 4 };
+        assert_eq!(Color::BLACK, integrator.color_at(&world, &ray));
+    }
+
+    #[test]
+    fn path_tracing_with_zero_samples_traces_one_path() {
+        let world = World::empty().with_background(Color::WHITE);
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 1, 0));
+        let integrator = Integrator::PathTraced { samples: 0 };
+        assert_eq!(Color::WHITE, integrator.color_at(&world, &ray));
+    }
+
+    #[test]
+    fn ambient_occlusion_integrator_renders_grayscale() {
+        let world = World::default();
+        let integrator = Integrator::AmbientOcclusion(AmbientOcclusion::new(8, 1.0));
+
+        let miss = Ray::new(Point::at(0, 0, -5), Vector::new(0, 1, 0));
+        assert_eq!(Color::WHITE, integrator.color_at(&world, &miss));
+
+        // The outer sphere is convex, so nothing hides any of it.
+        let hit = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+        assert_eq!(Color::WHITE, integrator.color_at(&world, &hit));
+    }
+}
@@ -3,9 +3,11 @@ use crate::geometry::point::Point;
 use crate::geometry::vector::Vector;
 use crate::helpers::approximate;
 use crate::helpers::approximate::Approximate;
-use crate::tracing::point_light::PointLight;
+use crate::tracing::light::Light;
+use crate::tracing::material::Material;
 use crate::tracing::ray::Ray;
-use crate::tracing::shapes::shape::{Shape, WorldShape};
+use crate::tracing::sampling;
+use crate::tracing::shapes::shape::WorldShape;
 use num::traits::Pow;
 use std::ops::Index;
 use std::slice::Iter;
@@ -92,9 +94,16 @@ pub struct PreComputedIntersection<'a> {
     reflect_vector: Vector,
     n1: f64,
     n2: f64,
+    // The solids (if any) that light arrives through, and that a refracted ray travels through.
+    previous_medium: Option<WorldShape<'a>>,
+    refracted_medium: Option<WorldShape<'a>>,
 }
 
 impl<'a> PreComputedIntersection<'a> {
+    pub fn material(&self) -> &Material {
+        self.thing.material()
+    }
+
     pub fn is_reflective(&self) -> bool {
         self.thing.material().reflective() > 0.0
     }
@@ -115,13 +124,66 @@ impl<'a> PreComputedIntersection<'a> {
         color * self.thing.material().transparency()
     }
 
-    pub fn lighting(&self, light: &PointLight, in_shadow: bool) -> Color {
-        self.thing.lighting(
+    /**
+     * The fraction of each color of light left after travelling distance along the refracted
+     * ray, through the medium beyond the surface. None, for rays that hit nothing, loses nothing.
+     */
+    pub fn refracted_transmittance(&self, distance: Option<f64>) -> Color {
+        transmittance_through(self.refracted_medium, distance)
+    }
+
+    /**
+     * As refracted_transmittance, but for rays leaving on the near side of the surface (reflected
+     * or diffusely scattered), so travelling back through the medium the light arrived by.
+     */
+    pub fn reflected_transmittance(&self, distance: Option<f64>) -> Color {
+        transmittance_through(self.previous_medium, distance)
+    }
+
+    /// Whether either side of the surface bends each color of light by a different amount.
+    pub fn is_dispersive(&self) -> bool {
+        [self.previous_medium, self.refracted_medium]
+            .iter()
+            .flatten()
+            .any(|medium| medium.material().is_dispersive())
+    }
+
+    /// The same intersection, with the refractive indexes for light of the wavelength provided.
+    pub fn at_wavelength(&self, wavelength: f64) -> PreComputedIntersection<'a> {
+        let refractive_index_of = |medium: Option<WorldShape>| {
+            medium.map_or(1.0, |it| it.material().refractive_index_at(wavelength))
+        };
+        PreComputedIntersection {
+            n1: refractive_index_of(self.previous_medium),
+            n2: refractive_index_of(self.refracted_medium),
+            ..*self
+        }
+    }
+
+    pub fn ambient_lighting(&self, light: &dyn Light) -> Color {
+        self.thing.ambient_lighting(light, self.over_point)
+    }
+
+    pub fn direct_lighting(&self, light: &dyn Light, light_intensity: f64) -> Color {
+        self.thing.direct_lighting(
             light,
             self.over_point,
             self.eye_vector,
             self.normal_vector,
-            in_shadow,
+            light_intensity,
+        )
+    }
+
+    /// The fraction of each color of light scattered diffusely by the surface.
+    pub fn diffuse_color(&self) -> Color {
+        self.thing.color_at(self.over_point) * self.thing.material().diffuse()
+    }
+
+    /// A ray leaving the surface in a random direction, more likely near the normal (see sampling).
+    pub fn diffuse_ray(&self) -> Ray {
+        Ray::new(
+            self.over_point,
+            sampling::cosine_weighted_direction(self.normal_vector),
         )
     }
 
@@ -199,38 +261,91 @@ impl<'a> PreComputedIntersection<'a> {
     }
 }
 
+fn transmittance_through(medium: Option<WorldShape>, distance: Option<f64>) -> Color {
+    match (medium, distance) {
+        (Some(medium), Some(distance)) => medium.material().transmittance(distance),
+        _ => Color::WHITE,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Intersection<'a> {
     time: f64,
     thing: WorldShape<'a>,
+    // Barycentric coordinates of the hit, for shapes (triangles) that produce them.
+    uv: Option<(f64, f64)>,
+    // The solid whose boundary this crosses: the shape itself, unless it is part of a Csg.
+    solid: WorldShape<'a>,
 }
 
 impl<'a> PartialEq for Intersection<'a> {
     fn eq(&self, other: &Intersection) -> bool {
-        self.time.almost(other.time) && &self.thing == &other.thing
+        // Identity rather than equality, as Groups may well hold equal shapes in different places.
+        self.time.almost(other.time) && std::ptr::eq(self.thing, other.thing)
     }
 }
 
 impl<'a> Intersection<'a> {
     pub fn new(time: f64, thing: WorldShape) -> Intersection {
-        Intersection { time, thing }
+        Intersection {
+            time,
+            thing,
+            uv: None,
+            solid: thing,
+        }
+    }
+
+    pub fn with_uv(time: f64, thing: WorldShape, u: f64, v: f64) -> Intersection {
+        Intersection {
+            time,
+            thing,
+            uv: Some((u, v)),
+            solid: thing,
+        }
     }
 
     pub fn time(&self) -> f64 {
         self.time
     }
 
+    pub fn thing(&self) -> WorldShape<'a> {
+        self.thing
+    }
+
+    pub fn uv(&self) -> Option<(f64, f64)> {
+        self.uv
+    }
+
+    /// Attribute this intersection to the boundary of a larger solid (e.g. a Csg) containing thing.
+    pub fn within(self, solid: WorldShape<'a>) -> Intersection<'a> {
+        Intersection { solid, ..self }
+    }
+
+    pub fn solid(&self) -> WorldShape<'a> {
+        self.solid
+    }
+
+    /**
+     * The normal of the intersected shape at the point provided, taking u/v into account if present.
+     */
+    pub fn normal_at(&self, point: Point) -> Vector {
+        match self.uv {
+            Some((u, v)) => self.thing.normal_at_uv(point, u, v),
+            None => self.thing.normal_at(point),
+        }
+    }
+
     /**
      * Calculate the PreComputed details.
      */
     pub fn pre_computations(
         &self,
         ray: &Ray,
-        intersections: &Intersections,
-    ) -> PreComputedIntersection {
+        intersections: &Intersections<'a>,
+    ) -> PreComputedIntersection<'a> {
         let point = ray.position(self.time);
         let eye_vector = -ray.direction();
-        let normal_vector = self.thing.normal_at(point).normalize();
+        let normal_vector = self.normal_at(point).normalize();
 
         let inside = normal_vector.dot(eye_vector) < 0.0;
         let normal_vector = if inside {
@@ -239,12 +354,18 @@ impl<'a> Intersection<'a> {
             normal_vector
         };
 
+        // Offset by the true normal, as bumps are only skin deep.
         let over_point = point + normal_vector * approximate::EPSILON;
         let under_point = point - normal_vector * approximate::EPSILON;
+        let normal_vector = self.thing.shading_normal(point, normal_vector);
 
         let reflect_vector = ray.direction().reflect(normal_vector);
 
-        let (n1, n2) = self.find_refractive_indexes(intersections);
+        let (previous_medium, refracted_medium) = self.find_media(intersections);
+        let refractive_index_of =
+            |medium: Option<WorldShape>| medium.map_or(1.0, |it| it.material().refractive_index());
+        let n1 = refractive_index_of(previous_medium);
+        let n2 = refractive_index_of(refracted_medium);
 
         PreComputedIntersection {
             time: self.time,
@@ -258,43 +379,46 @@ impl<'a> Intersection<'a> {
             reflect_vector,
             n1,
             n2,
+            previous_medium,
+            refracted_medium,
         }
     }
 
     /**
-     * Find the refractive index on each side of the intersection.
+     * The shapes (through which the solids were entered) that light is passing through on either
+     * side of this intersection, with None for empty space.
      */
-    fn find_refractive_indexes(&self, intersections: &Intersections) -> (f64, f64) {
-        let mut containers: Vec<&Shape> = Vec::new();
-        let mut n1 = 0.0;
-        let mut n2 = 0.0;
+    fn find_media(
+        &self,
+        intersections: &Intersections<'a>,
+    ) -> (Option<WorldShape<'a>>, Option<WorldShape<'a>>) {
+        // Track the solids we are inside, along with the surface through which each was entered.
+        let mut containers: Vec<(WorldShape<'a>, WorldShape<'a>)> = Vec::new();
+        let mut before = None;
+        let mut after = None;
 
         for intersection in intersections.iter() {
             if intersection == self {
-                n1 = containers
-                    .last()
-                    .map_or(1.0, |it| it.material().refractive_index());
+                before = containers.last().map(|(_, it)| *it);
             }
 
-            if containers.contains(&intersection.thing) {
-                let remove_index = containers
-                    .iter()
-                    .position(|it| it == &intersection.thing)
-                    .unwrap();
-                containers.remove(remove_index);
-            } else {
-                containers.push(intersection.thing);
+            let position = containers
+                .iter()
+                .position(|(solid, _)| std::ptr::eq(*solid, intersection.solid));
+            match position {
+                Some(remove_index) => {
+                    containers.remove(remove_index);
+                }
+                None => containers.push((intersection.solid, intersection.thing)),
             }
 
             if intersection == self {
-                n2 = containers
-                    .last()
-                    .map_or(1.0, |it| it.material().refractive_index());
+                after = containers.last().map(|(_, it)| *it);
                 break;
             }
         }
 
-        (n1, n2)
+        (before, after)
     }
 }
 
@@ -306,9 +430,11 @@ mod tests {
     use crate::geometry::vector::Vector;
     use crate::helpers::approximate;
     use crate::helpers::approximate::Approximate;
+    use crate::tracing::bump_map::BumpMap;
     use crate::tracing::intersection::{Intersection, Intersections};
     use crate::tracing::material::Material;
     use crate::tracing::ray::Ray;
+    use crate::tracing::shapes::group::Group;
     use crate::tracing::shapes::shape::{Shape, ShapeGeometry, WorldShape};
     use crate::tracing::shapes::sphere::Sphere;
     use num::integer::Roots;
@@ -571,6 +697,53 @@ mod tests {
         assert!(details.point.z < details.under_point.z);
     }
 
+    #[test]
+    fn equal_shapes_placed_by_different_groups_are_different_media() {
+        let glass =
+            || Shape::sphere().with_material(Material::default().with_refractive_index(1.5));
+        let first = Group::new(vec![glass()]).into_shape();
+        let second = Group::new(vec![glass()])
+            .into_shape()
+            .with_transform(translation(0, 0, 1));
+
+        let ray = Ray::new(Point::at(0, 0, -4), Vector::new(0, 0, 1));
+        let intersections = Intersections::new(
+            first
+                .intersect(&ray)
+                .into_iter()
+                .chain(second.intersect(&ray).into_iter())
+                .collect(),
+        );
+
+        // Leaving the first sphere, while still inside the second.
+        let leaving_first = &intersections[2];
+        assert!(leaving_first.time().almost(5.0));
+        let details = leaving_first.pre_computations(&ray, &intersections);
+        assert!(details.n1().almost(1.5));
+        assert!(details.n2().almost(1.5));
+    }
+
+    #[test]
+    fn bumps_tilt_the_normal_but_not_the_offset_points() {
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+        let material = Material::default().with_bump_map(BumpMap::noise(0.3, 0.5));
+        let shape = Shape::sphere().with_material(material);
+        let intersection = Intersection::new(4.0, &shape);
+        let details = intersection.pre_computations(&ray, &Intersections::empty());
+
+        let geometric_normal = Vector::new(0, 0, -1);
+        assert_ne!(geometric_normal, details.normal_vector);
+        assert!(details.normal_vector.magnitude().almost(1.0));
+        assert_eq!(
+            details.point + geometric_normal * approximate::EPSILON,
+            details.over_point
+        );
+        assert_eq!(
+            ray.direction().reflect(details.normal_vector),
+            details.reflect_vector
+        );
+    }
+
     #[test]
     fn schlick_with_total_internal_reflection() {
         let glass = Material::default()
@@ -0,0 +1,54 @@
+use std::fmt::Debug;
+
+use crate::display::color::Color;
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+
+/// A source of light in the World. Lights are sampled one or more times from each point being
+/// lit, both when shading that point and when testing whether it is in shadow.
+pub trait Light: Send + Sync + Debug {
+    fn intensity(&self) -> Color;
+
+    /// The fraction (0.0 to 1.0) of the light's intensity heading toward the point provided,
+    /// before shadows are considered. Only lights that aim somewhere (e.g. spotlights) need this.
+    fn falloff_at(&self, _point: Point) -> f64 {
+        1.0
+    }
+
+    /// Where the light comes from, as seen from the point provided.
+    /// Averaging over several samples produces soft shadows.
+    fn samples_from(&self, point: Point) -> Vec<LightSample>;
+}
+
+/**
+ * One sample of a light: the direction to it from a point being lit, and how far away it is.
+ * Lights with no position (e.g. the sun) are infinitely far away.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LightSample {
+    direction: Vector,
+    distance: f64,
+}
+
+impl LightSample {
+    pub fn new(direction: Vector, distance: f64) -> LightSample {
+        LightSample {
+            direction: direction.normalize(),
+            distance,
+        }
+    }
+
+    /// The sample of a light at light_position, as seen from the point provided.
+    pub fn between(point: Point, light_position: Point) -> LightSample {
+        let point_to_light = light_position - point;
+        LightSample::new(point_to_light, point_to_light.magnitude())
+    }
+
+    pub fn direction(&self) -> Vector {
+        self.direction
+    }
+
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+}
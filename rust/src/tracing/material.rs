@@ -2,9 +2,21 @@ use crate::display::color::Color;
 use crate::geometry::point::Point;
 use crate::geometry::vector::Vector;
 use crate::helpers::approximate::Approximate;
+use crate::tracing::bump_map::BumpMap;
+use crate::tracing::dispersion;
+use crate::tracing::light::Light;
+use crate::tracing::microfacet::Microfacet;
 use crate::tracing::patterns::pattern::{Pattern, PatternType};
 use crate::tracing::patterns::solid::Solid;
-use crate::tracing::point_light::PointLight;
+
+/// The model Material uses to work out how light reflects off it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shading {
+    /// Classic Phong, using the ambient, diffuse, specular and shininess settings.
+    Phong,
+    /// Physically based, with the material's color as base color. Still uses the ambient setting.
+    Microfacet(Microfacet),
+}
 
 #[derive(Debug, Clone)]
 pub struct Material {
@@ -16,6 +28,14 @@ pub struct Material {
     reflective: f64,
     transparency: f64,
     refractive_index: f64,
+    // How little the refractive index varies with wavelength, if it varies at all.
+    abbe_number: Option<f64>,
+    // Light given off by the material itself, regardless of any light sources.
+    emission: Color,
+    // How much of each color of light is absorbed per unit of distance travelled inside.
+    absorption: Color,
+    bump_map: Option<BumpMap>,
+    shading: Shading,
 }
 
 impl Material {
@@ -29,6 +49,11 @@ impl Material {
             reflective: 0.0,
             transparency: 0.0,
             refractive_index: 1.0,
+            abbe_number: None,
+            emission: Color::BLACK,
+            absorption: Color::BLACK,
+            bump_map: None,
+            shading: Shading::Phong,
         }
     }
 
@@ -51,6 +76,11 @@ impl Material {
             reflective,
             transparency,
             refractive_index,
+            abbe_number: None,
+            emission: Color::BLACK,
+            absorption: Color::BLACK,
+            bump_map: None,
+            shading: Shading::Phong,
         }
     }
 
@@ -120,6 +150,58 @@ impl Material {
         }
     }
 
+    /**
+     * Split light refracted by the material into its colors (see dispersion), with the lower
+     * the Abbe number the wider the rainbow. The refractive index then applies to yellow light.
+     */
+    pub fn with_abbe_number(self, abbe_number: f64) -> Material {
+        Material {
+            abbe_number: Some(abbe_number),
+            ..self
+        }
+    }
+
+    /**
+     * Make the material glow with the color provided. Under path tracing, light given off
+     * by emissive shapes illuminates everything around them.
+     */
+    pub fn with_emission(self, emission: Color) -> Material {
+        Material { emission, ..self }
+    }
+
+    /**
+     * Tint light passing through a transparent material, more so the further it travels inside
+     * (following the Beer-Lambert law). Light of each channel fades to 1/e of its strength
+     * after travelling 1 / (that channel of absorption) units, so absorbing red gives a blue-green.
+     */
+    pub fn with_absorption(self, absorption: Color) -> Material {
+        Material { absorption, ..self }
+    }
+
+    /// Shade as though the surface were bumpy, by tilting its normals.
+    pub fn with_bump_map(self, bump_map: BumpMap) -> Material {
+        Material {
+            bump_map: Some(bump_map),
+            ..self
+        }
+    }
+
+    /// Shade with the physically based model (see Microfacet) in place of Phong.
+    pub fn with_microfacet(self, metallic: f64, roughness: f64) -> Material {
+        Material {
+            shading: Shading::Microfacet(Microfacet::new(metallic, roughness)),
+            ..self
+        }
+    }
+
+    /// How much of the light reaching the material it scatters diffusely. Metals scatter none.
+    pub fn diffuse(&self) -> f64 {
+        match self.shading {
+            Shading::Phong => self.diffuse,
+            Shading::Microfacet(microfacet) => 1.0 - microfacet.metallic(),
+        }
+    }
+
     pub fn reflective(&self) -> f64 {
         self.reflective
     }
@@ -132,27 +214,118 @@ impl Material {
         self.refractive_index
     }
 
+    pub fn is_dispersive(&self) -> bool {
+        self.abbe_number.is_some()
+    }
+
+    /// The refractive index for light of the wavelength provided (in micrometres).
+    pub fn refractive_index_at(&self, wavelength: f64) -> f64 {
+        match self.abbe_number {
+            Some(abbe_number) => {
+                dispersion::refractive_index_at(self.refractive_index, abbe_number, wavelength)
+            }
+            None => self.refractive_index,
+        }
+    }
+
+    pub fn emission(&self) -> Color {
+        self.emission
+    }
+
+    pub fn absorption(&self) -> Color {
+        self.absorption
+    }
+
+    /// The fraction of each color of light left after travelling distance through the material.
+    pub fn transmittance(&self, distance: f64) -> Color {
+        let (red, green, blue) = self.absorption.channels();
+        let remaining = |absorption: f64| (-absorption * distance).exp();
+        Color::new(remaining(red), remaining(green), remaining(blue))
+    }
+
+    pub fn bump_map(&self) -> Option<&BumpMap> {
+        self.bump_map.as_ref()
+    }
+
+    pub fn shading(&self) -> Shading {
+        self.shading
+    }
+
+    /**
+     * The color of this material when lit by the light provided. The light_intensity is the
+     * fraction (0.0 to 1.0) of the light reaching the position, i.e. not blocked by shadows.
+     * Lights are sampled from the (world space) position, while the pattern is looked up at
+     * the same point in object space, object_position.
+     */
     pub fn lighting(
         &self,
-        light: &PointLight,
+        light: &dyn Light,
         position: Point,
+        object_position: Point,
         eye_vector: Vector,
         normal: Vector,
-        in_shadow: bool,
+        light_intensity: f64,
     ) -> Color {
-        let point = position;
-        let effective_color = self.pattern.pattern_at(point) * light.intensity();
+        self.ambient_lighting(light, object_position)
+            + self.direct_lighting(
+                light,
+                position,
+                object_position,
+                eye_vector,
+                normal,
+                light_intensity,
+            )
+    }
+
+    /// The flat approximation of light bouncing around the scene, that lighting adds everywhere.
+    pub fn ambient_lighting(&self, light: &dyn Light, object_position: Point) -> Color {
+        let effective_color = self.pattern.pattern_at(object_position) * light.intensity();
+        self.ambient_contribution(effective_color)
+    }
 
-        let ambient = self.ambient_contribution(effective_color);
-        let diffuse = match in_shadow {
-            true => Color::BLACK,
-            false => self.diffuse_contribution(light, position, normal, effective_color),
-        };
-        let specular = match in_shadow {
-            true => Color::BLACK,
-            false => self.specular_contribution(light, position, eye_vector, normal),
-        };
-        ambient + diffuse + specular
+    /// The color of the material at the (object space) position provided, before any lighting.
+    pub fn color_at(&self, position: Point) -> Color {
+        self.pattern.pattern_at(position)
+    }
+
+    /**
+     * The diffuse and specular light reaching the eye directly from the light provided,
+     * i.e. lighting without the ambient term approximating everything else.
+     */
+    pub fn direct_lighting(
+        &self,
+        light: &dyn Light,
+        position: Point,
+        object_position: Point,
+        eye_vector: Vector,
+        normal: Vector,
+        light_intensity: f64,
+    ) -> Color {
+        if light_intensity <= 0.0 {
+            return Color::BLACK;
+        }
+        let color = self.pattern.pattern_at(object_position);
+        let effective_color = color * light.intensity();
+
+        let samples = light.samples_from(position);
+        let lit = samples
+            .iter()
+            .map(|sample| {
+                let light_direction = sample.direction();
+                match self.shading {
+                    Shading::Phong => {
+                        self.diffuse_contribution(light_direction, normal, effective_color)
+                            + self.specular_contribution(light, light_direction, eye_vector, normal)
+                    }
+                    Shading::Microfacet(microfacet) => {
+                        microfacet.reflectance(color, light_direction, eye_vector, normal)
+                            * light.intensity()
+                    }
+                }
+            })
+            .fold(Color::BLACK, |total, color| total + color);
+
+        lit * (light_intensity / samples.len() as f64)
     }
 
     fn ambient_contribution(&self, effective_color: Color) -> Color {
@@ -161,12 +334,10 @@ impl Material {
 
     fn diffuse_contribution(
         &self,
-        light: &PointLight,
-        position: Point,
+        light_direction: Vector,
         normal: Vector,
         effective_color: Color,
     ) -> Color {
-        let light_direction = (light.position() - position).normalize();
         let light_dot_normal = light_direction.dot(normal);
         if light_dot_normal < 0.0 {
             Color::BLACK
@@ -177,12 +348,11 @@ impl Material {
 
     fn specular_contribution(
         &self,
-        light: &PointLight,
-        position: Point,
+        light: &dyn Light,
+        light_direction: Vector,
         eye_vector: Vector,
         normal: Vector,
     ) -> Color {
-        let light_direction = (light.position() - position).normalize();
         let light_dot_normal = light_direction.dot(normal);
         if light_dot_normal < 0.0 {
             Color::BLACK
@@ -207,6 +377,11 @@ impl PartialEq for Material {
             && self.diffuse.almost(other.diffuse)
             && self.specular.almost(other.specular)
             && self.shininess.almost(other.shininess)
+            && self.emission == other.emission
+            && self.abbe_number == other.abbe_number
+            && self.absorption == other.absorption
+            && self.bump_map == other.bump_map
+            && self.shading == other.shading
     }
 }
 impl Eq for Material {}
@@ -217,6 +392,9 @@ mod test {
     use crate::geometry::point::Point;
     use crate::geometry::transformations;
     use crate::geometry::vector::Vector;
+    use crate::tracing::area_light::AreaLight;
+    use crate::tracing::bump_map::BumpMap;
+    use crate::tracing::dispersion::{BLUE_WAVELENGTH, RED_WAVELENGTH};
     use crate::tracing::intersection::{Intersection, Intersections};
     use crate::tracing::material::Material;
     use crate::tracing::patterns::stripe_pattern::StripePattern;
@@ -224,6 +402,7 @@ mod test {
     use crate::tracing::ray::Ray;
     use crate::tracing::shapes::shape::ShapeGeometry;
     use crate::tracing::shapes::sphere::Sphere;
+    use std::f64::consts::FRAC_1_SQRT_2;
 
     #[test]
     fn default_material() {
@@ -242,8 +421,15 @@ mod test {
         let material = &Material::default();
         let light_argument = &light;
         let position = Point::origin();
-        let in_shadow = false;
-        let result = material.lighting(light_argument, position, eye_vector, normal, in_shadow);
+        let light_intensity = 1.0;
+        let result = material.lighting(
+            light_argument,
+            position,
+            position,
+            eye_vector,
+            normal,
+            light_intensity,
+        );
         assert_eq!(Color::new(1.9, 1.9, 1.9), result);
     }
 
@@ -256,8 +442,15 @@ mod test {
         let material = &Material::default();
         let light_argument = &light;
         let position = Point::origin();
-        let in_shadow = false;
-        let result = material.lighting(light_argument, position, eye_vector, normal, in_shadow);
+        let light_intensity = 1.0;
+        let result = material.lighting(
+            light_argument,
+            position,
+            position,
+            eye_vector,
+            normal,
+            light_intensity,
+        );
         assert_eq!(Color::new(1, 1, 1), result);
     }
 
@@ -270,8 +463,15 @@ mod test {
         let material = &Material::default();
         let light_argument = &light;
         let position = Point::origin();
-        let in_shadow = false;
-        let result = material.lighting(light_argument, position, eye_vector, normal, in_shadow);
+        let light_intensity = 1.0;
+        let result = material.lighting(
+            light_argument,
+            position,
+            position,
+            eye_vector,
+            normal,
+            light_intensity,
+        );
         assert_eq!(Color::new(0.7364, 0.7364, 0.7364), result);
     }
 
@@ -284,8 +484,15 @@ mod test {
         let material = &Material::default();
         let light_argument = &light;
         let position = Point::origin();
-        let in_shadow = false;
-        let result = material.lighting(light_argument, position, eye_vector, normal, in_shadow);
+        let light_intensity = 1.0;
+        let result = material.lighting(
+            light_argument,
+            position,
+            position,
+            eye_vector,
+            normal,
+            light_intensity,
+        );
         assert_eq!(Color::new(1.6364, 1.6364, 1.6364), result);
     }
 
@@ -298,8 +505,15 @@ mod test {
         let material = &Material::default();
         let light_argument = &light;
         let position = Point::origin();
-        let in_shadow = false;
-        let result = material.lighting(light_argument, position, eye_vector, normal, in_shadow);
+        let light_intensity = 1.0;
+        let result = material.lighting(
+            light_argument,
+            position,
+            position,
+            eye_vector,
+            normal,
+            light_intensity,
+        );
         assert_eq!(Color::new(0.1, 0.1, 0.1), result);
     }
 
@@ -308,15 +522,73 @@ mod test {
         let eye_vector = Vector::new(0, 0, -1);
         let normal = Vector::new(0, 0, -1);
         let light = PointLight::new(Point::at(0, 0, -10), Color::new(1, 1, 1));
-        let in_shadow = true;
+        let light_intensity = 0.0;
 
         let material = &Material::default();
         let light_argument = &light;
         let position = Point::origin();
-        let result = material.lighting(light_argument, position, eye_vector, normal, in_shadow);
+        let result = material.lighting(
+            light_argument,
+            position,
+            position,
+            eye_vector,
+            normal,
+            light_intensity,
+        );
         assert_eq!(Color::new(0.1, 0.1, 0.1), result);
     }
 
+    #[test]
+    fn lighting_with_partially_shadowed_surface() {
+        let eye_vector = Vector::new(0, 0, -1);
+        let normal = Vector::new(0, 0, -1);
+        let light = PointLight::new(Point::at(0, 0, -10), Color::new(1, 1, 1));
+
+        let material = &Material::default();
+        let result = material.lighting(
+            &light,
+            Point::origin(),
+            Point::origin(),
+            eye_vector,
+            normal,
+            0.5,
+        );
+        assert_eq!(Color::new(1.0, 1.0, 1.0), result);
+    }
+
+    #[test]
+    fn lighting_samples_area_light() {
+        let light = AreaLight::new(
+            Point::at(-0.5, -0.5, -5.0),
+            Vector::new(1, 0, 0),
+            2,
+            Vector::new(0, 1, 0),
+            2,
+            Color::WHITE,
+        )
+        .without_jitter();
+        let material = Material::default()
+            .with_ambient(0.1)
+            .with_diffuse(0.9)
+            .with_specular(0.0);
+        let eye = Point::at(0, 0, -5);
+
+        let cases = vec![
+            (Point::at(0, 0, -1), Color::new(0.9965, 0.9965, 0.9965)),
+            (
+                Point::at(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+                Color::new(0.62318, 0.62318, 0.62318),
+            ),
+        ];
+
+        for (position, expected) in cases {
+            let eye_vector = (eye - position).normalize();
+            let normal = position - Point::origin();
+            let result = material.lighting(&light, position, position, eye_vector, normal, 1.0);
+            assert_eq!(expected, result);
+        }
+    }
+
     #[test]
     fn lighting_with_pattern() {
         let pattern = StripePattern::new(Color::WHITE, Color::BLACK);
@@ -327,19 +599,109 @@ mod test {
         let material_argument = &material;
         let light_argument = &light;
         let position = Point::at(0.9, 0.0, 0.0);
-        let in_shadow = false;
-        let color_one =
-            material_argument.lighting(light_argument, position, eye_vector, normal, in_shadow);
+        let light_intensity = 1.0;
+        let color_one = material_argument.lighting(
+            light_argument,
+            position,
+            position,
+            eye_vector,
+            normal,
+            light_intensity,
+        );
         let material_argument = &material;
         let light_argument = &light;
         let position = Point::at(1.1, 0.0, 0.0);
-        let in_shadow = false;
-        let color_two =
-            material_argument.lighting(light_argument, position, eye_vector, normal, in_shadow);
+        let light_intensity = 1.0;
+        let color_two = material_argument.lighting(
+            light_argument,
+            position,
+            position,
+            eye_vector,
+            normal,
+            light_intensity,
+        );
         assert_eq!(Color::WHITE, color_one);
         assert_eq!(Color::BLACK, color_two);
     }
 
+    #[test]
+    fn microfacet_lighting_with_eye_between_light_and_surface() {
+        let eye_vector = Vector::new(0, 0, -1);
+        let normal = Vector::new(0, 0, -1);
+        let light = PointLight::new(Point::at(0, 0, -10), Color::WHITE);
+
+        let material = Material::default().with_microfacet(0.0, 1.0);
+        let result = material.lighting(
+            &light,
+            Point::origin(),
+            Point::origin(),
+            eye_vector,
+            normal,
+            1.0,
+        );
+        assert_eq!(Color::new(1.07, 1.07, 1.07), result);
+    }
+
+    #[test]
+    fn microfacet_metals_have_no_diffuse_light() {
+        let eye_vector = Vector::new(0, 0, -1);
+        let normal = Vector::new(0, 0, -1);
+        let light = PointLight::new(Point::at(0, 10, -10), Color::WHITE);
+
+        let metal = Material::default()
+            .with_ambient(0.0)
+            .with_microfacet(1.0, 0.1);
+        let result = metal.lighting(
+            &light,
+            Point::origin(),
+            Point::origin(),
+            eye_vector,
+            normal,
+            1.0,
+        );
+        assert!(result.max_channel() < 0.01);
+        assert_eq!(0.0, metal.diffuse());
+    }
+
+    #[test]
+    fn materials_differing_only_by_bump_map_are_not_equal() {
+        let bumpy = Material::default().with_bump_map(BumpMap::noise(0.5, 0.3));
+        assert_ne!(Material::default(), bumpy);
+        assert_ne!(
+            Material::default().with_bump_map(BumpMap::noise(0.5, 0.1)),
+            bumpy
+        );
+        assert_eq!(
+            Material::default().with_bump_map(BumpMap::noise(0.5, 0.3)),
+            bumpy
+        );
+    }
+
+    #[test]
+    fn transmittance_falls_with_distance_through_absorbing_material() {
+        let material = Material::default().with_absorption(Color::new(1.0, 0.5, 0.0));
+        assert_eq!(Color::WHITE, material.transmittance(0.0));
+        assert_eq!(
+            Color::new(0.13534, 0.36788, 1.0),
+            material.transmittance(2.0)
+        );
+        assert_eq!(Color::WHITE, Material::default().transmittance(100.0));
+    }
+
+    #[test]
+    fn refractive_index_varies_with_wavelength_given_abbe_number() {
+        let glass = Material::default().with_refractive_index(1.5);
+        assert_eq!(1.5, glass.refractive_index_at(RED_WAVELENGTH));
+        assert_eq!(1.5, glass.refractive_index_at(BLUE_WAVELENGTH));
+
+        let dispersive = glass.with_abbe_number(30.0);
+        assert!(dispersive.is_dispersive());
+        assert!(
+            dispersive.refractive_index_at(BLUE_WAVELENGTH)
+                > dispersive.refractive_index_at(RED_WAVELENGTH)
+        );
+    }
+
     #[test]
     fn finding_n1_and_n2_at_various_intersections() {
         let glass = Material::default().with_transparency(1.0);
@@ -0,0 +1,147 @@
+use crate::display::color::Color;
+use crate::geometry::vector::Vector;
+use std::f64::consts::PI;
+
+/// How much light a non-metal reflects head on, i.e. its Fresnel reflectance at 0 degrees.
+const DIELECTRIC_REFLECTANCE: f64 = 0.04;
+
+/// Keeps perfectly smooth surfaces (with infinitely small highlights) from dividing by zero.
+const MIN_ALPHA: f64 = 0.001;
+
+/**
+ * Physically based (Cook-Torrance) shading, treating the surface as countless tiny mirrors:
+ * a GGX distribution of their orientations, Smith geometry for how they shadow and mask each
+ * other, and Schlick's approximation of Fresnel reflectance. Both metallic and roughness run
+ * from 0.0 to 1.0; metals tint their reflections with the base color and have no diffuse light.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Microfacet {
+    metallic: f64,
+    roughness: f64,
+}
+
+impl Microfacet {
+    pub fn new(metallic: f64, roughness: f64) -> Microfacet {
+        Microfacet {
+            metallic: metallic.clamp(0.0, 1.0),
+            roughness: roughness.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn metallic(&self) -> f64 {
+        self.metallic
+    }
+
+    pub fn roughness(&self) -> f64 {
+        self.roughness
+    }
+
+    /**
+     * The share of light arriving from light_direction that is reflected towards the eye, for
+     * each channel. It is scaled by pi, so that like Phong a white light shining straight onto a
+     * white matte surface lights it fully, rather than to 1/pi.
+     */
+    pub fn reflectance(
+        &self,
+        base_color: Color,
+        light_direction: Vector,
+        eye_vector: Vector,
+        normal: Vector,
+    ) -> Color {
+        let light_dot_normal = light_direction.dot(normal);
+        let eye_dot_normal = eye_vector.dot(normal);
+        if light_dot_normal <= 0.0 || eye_dot_normal <= 0.0 {
+            return Color::BLACK;
+        }
+        let halfway = (light_direction + eye_vector).normalize();
+
+        let fresnel = self.fresnel(base_color, eye_vector.dot(halfway));
+        let specular = fresnel
+            * (self.distribution(normal.dot(halfway))
+                * self.geometry(light_dot_normal, eye_dot_normal)
+                / (4.0 * light_dot_normal * eye_dot_normal));
+        let diffuse = (Color::WHITE - fresnel) * base_color * ((1.0 - self.metallic) / PI);
+
+        (diffuse + specular) * (PI * light_dot_normal)
+    }
+
+    /// GGX (Trowbridge-Reitz): how many microfacets face along the halfway vector.
+    fn distribution(&self, normal_dot_halfway: f64) -> f64 {
+        let alpha = (self.roughness * self.roughness).max(MIN_ALPHA);
+        let alpha2 = alpha * alpha;
+        let denominator = normal_dot_halfway * normal_dot_halfway * (alpha2 - 1.0) + 1.0;
+        alpha2 / (PI * denominator * denominator)
+    }
+
+    /// Smith (Schlick-GGX): the fraction of microfacets seen by both the light and the eye.
+    fn geometry(&self, light_dot_normal: f64, eye_dot_normal: f64) -> f64 {
+        let k = (self.roughness + 1.0).powi(2) / 8.0;
+        let unblocked = |cos: f64| cos / (cos * (1.0 - k) + k);
+        unblocked(light_dot_normal) * unblocked(eye_dot_normal)
+    }
+
+    /// Schlick: reflectance rising from its head on value to white at grazing angles.
+    fn fresnel(&self, base_color: Color, cos: f64) -> Color {
+        let dielectric = Color::new(
+            DIELECTRIC_REFLECTANCE,
+            DIELECTRIC_REFLECTANCE,
+            DIELECTRIC_REFLECTANCE,
+        );
+        let head_on = dielectric * (1.0 - self.metallic) + base_color * self.metallic;
+        head_on + (Color::WHITE - head_on) * (1.0 - cos.clamp(0.0, 1.0)).powi(5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::microfacet::Microfacet;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    #[test]
+    fn rough_dielectric_lit_head_on() {
+        let head_on = Vector::new(0, 0, -1);
+        let microfacet = Microfacet::new(0.0, 1.0);
+        let result = microfacet.reflectance(Color::WHITE, head_on, head_on, head_on);
+        assert_eq!(Color::new(0.97, 0.97, 0.97), result);
+    }
+
+    #[test]
+    fn metals_reflect_only_their_own_color() {
+        let head_on = Vector::new(0, 0, -1);
+        let microfacet = Microfacet::new(1.0, 0.5);
+        let result = microfacet.reflectance(Color::RED, head_on, head_on, head_on);
+        assert_eq!(Color::new(4.0, 0.0, 0.0), result);
+    }
+
+    #[test]
+    fn smoother_surfaces_have_tighter_highlights() {
+        let normal = Vector::new(0, 0, -1);
+        let light = Vector::new(0, 0, -1);
+        let in_highlight = Vector::new(0, 0, -1);
+        let off_highlight = Vector::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
+
+        let smooth = Microfacet::new(1.0, 0.2);
+        let rough = Microfacet::new(1.0, 0.8);
+        let reflectance =
+            |microfacet: Microfacet, eye| microfacet.reflectance(Color::WHITE, light, eye, normal);
+
+        assert!(
+            reflectance(smooth, in_highlight).max_channel()
+                > reflectance(rough, in_highlight).max_channel()
+        );
+        assert!(
+            reflectance(smooth, off_highlight).max_channel()
+                < reflectance(rough, off_highlight).max_channel()
+        );
+    }
+
+    #[test]
+    fn no_light_from_behind_the_surface() {
+        let normal = Vector::new(0, 0, -1);
+        let microfacet = Microfacet::new(0.0, 0.5);
+        let result = microfacet.reflectance(Color::WHITE, Vector::new(0, 0, 1), normal, normal);
+        assert_eq!(Color::BLACK, result);
+    }
+}
@@ -1,10 +1,25 @@
+pub mod ambient_occlusion;
+pub mod area_light;
+pub mod backgrounds;
+pub mod bounding_box;
+pub mod bump_map;
+pub mod bvh;
 pub mod camera;
+pub mod directional_light;
+pub mod dispersion;
+pub mod integrator;
 pub mod intersection;
+pub mod light;
 pub mod material;
+pub mod microfacet;
+pub mod obj_parser;
 pub mod patterns;
 pub mod point_light;
+pub mod projections;
 pub mod ray;
+pub mod sampling;
 pub mod shapes;
+pub mod spot_light;
 pub mod world;
 
 #[cfg(test)]
@@ -0,0 +1,556 @@
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::tracing::shapes::group::Group;
+use crate::tracing::shapes::shape::{Shape, ShapeGeometry};
+use crate::tracing::shapes::smooth_triangle::SmoothTriangle;
+use crate::tracing::shapes::triangle::Triangle;
+
+const DEFAULT_GROUP: &str = "default";
+
+/// The indexes making up one corner of a face. Texture coordinates are validated but not kept.
+#[derive(Debug, Clone, Copy)]
+struct FaceVertex {
+    vertex: usize,
+    normal: Option<usize>,
+}
+
+/// Reads Wavefront OBJ content into triangles.
+///
+/// Supports vertices (v), vertex normals (vn), texture coordinates (vt), faces (f) and
+/// named groups (g). Polygons are fan-triangulated. Anything else, including malformed
+/// lines, is skipped and counted rather than treated as an error.
+#[derive(Debug)]
+pub struct ObjParser {
+    vertices: Vec<Point>,
+    normals: Vec<Vector>,
+    texture_coordinates: Vec<(f64, f64)>,
+    groups: Vec<(String, Vec<Shape>)>,
+    // Index into groups of the one that faces are currently added to.
+    current_group: usize,
+    ignored_lines: usize,
+}
+
+impl ObjParser {
+    pub fn parse(content: &str) -> ObjParser {
+        let mut parser = ObjParser {
+            vertices: Vec::new(),
+            normals: Vec::new(),
+            texture_coordinates: Vec::new(),
+            groups: vec![(String::from(DEFAULT_GROUP), Vec::new())],
+            current_group: 0,
+            ignored_lines: 0,
+        };
+
+        for line in content.lines() {
+            if !parser.parse_line(line) {
+                parser.ignored_lines += 1;
+            }
+        }
+
+        parser
+    }
+
+    /// The number of lines that were not understood and so skipped.
+    pub fn ignored_lines(&self) -> usize {
+        self.ignored_lines
+    }
+
+    /// Vertices are numbered from 1, as they are in the file. None if there is no such vertex.
+    pub fn vertex(&self, index: usize) -> Option<Point> {
+        numbered_from_one(&self.vertices, index)
+    }
+
+    /// Normals are numbered from 1, as they are in the file. None if there is no such normal.
+    pub fn normal(&self, index: usize) -> Option<Vector> {
+        numbered_from_one(&self.normals, index)
+    }
+
+    /// Texture coordinates are numbered from 1, as they are in the file. None if there are none.
+    pub fn texture_coordinate(&self, index: usize) -> Option<(f64, f64)> {
+        numbered_from_one(&self.texture_coordinates, index)
+    }
+
+    /// The triangles appearing before any named group.
+    pub fn default_group(&self) -> &[Shape] {
+        self.group(DEFAULT_GROUP).unwrap()
+    }
+
+    pub fn group(&self, name: &str) -> Option<&[Shape]> {
+        self.groups
+            .iter()
+            .find(|(group_name, _)| group_name == name)
+            .map(|(_, shapes)| shapes.as_slice())
+    }
+
+    pub fn group_names(&self) -> Vec<&str> {
+        self.groups.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// All triangles from every group, ready to be placed in a World.
+    pub fn into_shapes(self) -> Vec<Shape> {
+        self.groups
+            .into_iter()
+            .flat_map(|(_, shapes)| shapes.into_iter())
+            .collect()
+    }
+
+    /// All triangles as a single Group, with one child Group for each group in the file.
+    pub fn into_group(self) -> Shape {
+        let children = self
+            .groups
+            .into_iter()
+            .filter(|(_, shapes)| !shapes.is_empty())
+            .map(|(_, shapes)| Group::new(shapes).into_shape())
+            .collect();
+        Group::new(children).into_shape()
+    }
+
+    // Returns false if the line could not be used.
+    fn parse_line(&mut self, line: &str) -> bool {
+        let mut tokens = line.split_whitespace();
+        let keyword = match tokens.next() {
+            Some(keyword) => keyword,
+            // Blank lines carry no information, but are not a problem either.
+            None => return true,
+        };
+        let arguments: Vec<&str> = tokens.collect();
+
+        match keyword {
+            "v" => parse_floats(&arguments, 3)
+                .map(|values| {
+                    self.vertices
+                        .push(Point::at(values[0], values[1], values[2]))
+                })
+                .is_some(),
+            "vn" => parse_floats(&arguments, 3)
+                .map(|values| {
+                    self.normals
+                        .push(Vector::new(values[0], values[1], values[2]))
+                })
+                .is_some(),
+            "vt" => parse_floats(&arguments, 2)
+                .map(|values| self.texture_coordinates.push((values[0], values[1])))
+                .is_some(),
+            "f" => self.parse_face(&arguments),
+            "g" if !arguments.is_empty() => {
+                self.start_group(arguments.join(" "));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Faces under a name seen before are added to that group, rather than a second one.
+    fn start_group(&mut self, name: String) {
+        self.current_group = match self.groups.iter().position(|(it, _)| *it == name) {
+            Some(index) => index,
+            None => {
+                self.groups.push((name, Vec::new()));
+                self.groups.len() - 1
+            }
+        };
+    }
+
+    fn parse_face(&mut self, arguments: &[&str]) -> bool {
+        let corners: Option<Vec<FaceVertex>> = arguments
+            .iter()
+            .map(|argument| self.parse_face_vertex(argument))
+            .collect();
+
+        let corners = match corners {
+            Some(corners) if corners.len() >= 3 => corners,
+            _ => return false,
+        };
+
+        let triangles: Option<Vec<Shape>> = (1..corners.len() - 1)
+            .map(|index| self.make_triangle(corners[0], corners[index], corners[index + 1]))
+            .collect();
+        match triangles {
+            Some(triangles) => {
+                self.groups[self.current_group].1.extend(triangles);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Face vertices look like v, v/vt, v//vn or v/vt/vn.
+    fn parse_face_vertex(&self, argument: &str) -> Option<FaceVertex> {
+        let mut parts = argument.split('/');
+        let vertex = resolve_index(parts.next()?, self.vertices.len())?;
+
+        let texture = parts.next().filter(|part| !part.is_empty());
+        if let Some(texture) = texture {
+            resolve_index(texture, self.texture_coordinates.len())?;
+        }
+
+        let normal = match parts.next().filter(|part| !part.is_empty()) {
+            Some(normal) => Some(resolve_index(normal, self.normals.len())?),
+            None => None,
+        };
+
+        Some(FaceVertex { vertex, normal })
+    }
+
+    // None if the face refers to vertices or normals that do not exist.
+    fn make_triangle(&self, one: FaceVertex, two: FaceVertex, three: FaceVertex) -> Option<Shape> {
+        let (p1, p2, p3) = (
+            self.vertex(one.vertex)?,
+            self.vertex(two.vertex)?,
+            self.vertex(three.vertex)?,
+        );
+
+        let triangle = match (one.normal, two.normal, three.normal) {
+            (Some(n1), Some(n2), Some(n3)) => SmoothTriangle::new(
+                p1,
+                p2,
+                p3,
+                self.normal(n1)?,
+                self.normal(n2)?,
+                self.normal(n3)?,
+            )
+            .into_shape(),
+            _ => Triangle::new(p1, p2, p3).into_shape(),
+        };
+        Some(triangle)
+    }
+}
+
+fn numbered_from_one<T: Copy>(elements: &[T], index: usize) -> Option<T> {
+    elements.get(index.checked_sub(1)?).copied()
+}
+
+/// Parse exactly the first `count` arguments as floats, ignoring any extras (e.g. an optional w).
+fn parse_floats(arguments: &[&str], count: usize) -> Option<Vec<f64>> {
+    if arguments.len() < count {
+        return None;
+    }
+    arguments[..count]
+        .iter()
+        .map(|argument| argument.parse::<f64>().ok())
+        .collect()
+}
+
+/// Convert an OBJ index into a 1-based index, resolving negative (relative) indexes.
+/// Returns None if the index does not refer to an element read so far.
+fn resolve_index(value: &str, count: usize) -> Option<usize> {
+    let index: i64 = value.parse().ok()?;
+    let resolved = if index < 0 {
+        count as i64 + index + 1
+    } else {
+        index
+    };
+
+    if resolved >= 1 && resolved <= count as i64 {
+        Some(resolved as usize)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::point::Point;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::obj_parser::ObjParser;
+    use crate::tracing::ray::Ray;
+    use crate::tracing::shapes::shape::ShapeGeometry;
+    use crate::tracing::shapes::smooth_triangle::SmoothTriangle;
+    use crate::tracing::shapes::triangle::Triangle;
+
+    #[test]
+    fn ignoring_unrecognized_lines() {
+        let gibberish = "There was a young lady named Bright
+who traveled much faster than light.
+She set out one day
+in a relative way,
+and came back the previous night.";
+
+        let parser = ObjParser::parse(gibberish);
+        assert_eq!(5, parser.ignored_lines());
+        assert!(parser.default_group().is_empty());
+    }
+
+    #[test]
+    fn malformed_lines_are_ignored() {
+        let content = "v 1 2
+v 1 two 3
+f 1 2 3
+vn 0 1";
+
+        let parser = ObjParser::parse(content);
+        assert_eq!(4, parser.ignored_lines());
+    }
+
+    #[test]
+    fn vertex_records() {
+        let content = "v -1 1 0
+v -1.0000 0.5000 0.0000
+v 1 0 0
+v 1 1 0";
+
+        let parser = ObjParser::parse(content);
+        assert_eq!(0, parser.ignored_lines());
+        assert_eq!(Point::at(-1, 1, 0), parser.vertex(1).unwrap());
+        assert_eq!(Point::at(-1.0, 0.5, 0.0), parser.vertex(2).unwrap());
+        assert_eq!(Point::at(1, 0, 0), parser.vertex(3).unwrap());
+        assert_eq!(Point::at(1, 1, 0), parser.vertex(4).unwrap());
+    }
+
+    #[test]
+    fn parsing_triangle_faces() {
+        let content = "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+
+f 1 2 3
+f 1 3 4";
+
+        let parser = ObjParser::parse(content);
+        let triangles = parser.default_group();
+
+        assert_eq!(2, triangles.len());
+        assert_eq!(
+            Triangle::new(
+                parser.vertex(1).unwrap(),
+                parser.vertex(2).unwrap(),
+                parser.vertex(3).unwrap()
+            )
+            .into_shape(),
+            triangles[0]
+        );
+        assert_eq!(
+            Triangle::new(
+                parser.vertex(1).unwrap(),
+                parser.vertex(3).unwrap(),
+                parser.vertex(4).unwrap()
+            )
+            .into_shape(),
+            triangles[1]
+        );
+    }
+
+    #[test]
+    fn triangulating_polygons() {
+        let content = "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+v 0 2 0
+
+f 1 2 3 4 5";
+
+        let parser = ObjParser::parse(content);
+        let triangles = parser.default_group();
+
+        assert_eq!(3, triangles.len());
+        assert_eq!(
+            Triangle::new(
+                parser.vertex(1).unwrap(),
+                parser.vertex(2).unwrap(),
+                parser.vertex(3).unwrap()
+            )
+            .into_shape(),
+            triangles[0]
+        );
+        assert_eq!(
+            Triangle::new(
+                parser.vertex(1).unwrap(),
+                parser.vertex(3).unwrap(),
+                parser.vertex(4).unwrap()
+            )
+            .into_shape(),
+            triangles[1]
+        );
+        assert_eq!(
+            Triangle::new(
+                parser.vertex(1).unwrap(),
+                parser.vertex(4).unwrap(),
+                parser.vertex(5).unwrap()
+            )
+            .into_shape(),
+            triangles[2]
+        );
+    }
+
+    #[test]
+    fn triangles_in_groups() {
+        let content = "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+
+g FirstGroup
+f 1 2 3
+g SecondGroup
+f 1 3 4";
+
+        let parser = ObjParser::parse(content);
+        let first = parser.group("FirstGroup").unwrap();
+        let second = parser.group("SecondGroup").unwrap();
+
+        assert_eq!(
+            vec!["default", "FirstGroup", "SecondGroup"],
+            parser.group_names()
+        );
+        assert_eq!(
+            Triangle::new(
+                parser.vertex(1).unwrap(),
+                parser.vertex(2).unwrap(),
+                parser.vertex(3).unwrap()
+            )
+            .into_shape(),
+            first[0]
+        );
+        assert_eq!(
+            Triangle::new(
+                parser.vertex(1).unwrap(),
+                parser.vertex(3).unwrap(),
+                parser.vertex(4).unwrap()
+            )
+            .into_shape(),
+            second[0]
+        );
+        assert_eq!(2, parser.into_shapes().len());
+    }
+
+    #[test]
+    fn converting_obj_file_to_group() {
+        let content = "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+
+g FirstGroup
+f 1 2 3
+g SecondGroup
+f 1 3 4";
+
+        let group = ObjParser::parse(content).into_group();
+        let first = Ray::new(Point::at(-0.5, 0.25, -5.0), Vector::new(0, 0, 1));
+        let second = Ray::new(Point::at(0.5, 0.75, -5.0), Vector::new(0, 0, 1));
+
+        assert_eq!(1, group.intersect(&first).len());
+        assert_eq!(1, group.intersect(&second).len());
+    }
+
+    #[test]
+    fn vertex_normal_records() {
+        let content = "vn 0 0 1
+vn 0.707 0 -0.707
+vn 1 2 3";
+
+        let parser = ObjParser::parse(content);
+        assert_eq!(Vector::new(0, 0, 1), parser.normal(1).unwrap());
+        assert_eq!(Vector::new(0.707, 0.0, -0.707), parser.normal(2).unwrap());
+        assert_eq!(Vector::new(1, 2, 3), parser.normal(3).unwrap());
+    }
+
+    #[test]
+    fn texture_coordinate_records() {
+        let content = "vt 0.25 0.5
+vt 1 0 0";
+
+        let parser = ObjParser::parse(content);
+        assert_eq!(0, parser.ignored_lines());
+        assert_eq!((0.25, 0.5), parser.texture_coordinate(1).unwrap());
+        assert_eq!((1.0, 0.0), parser.texture_coordinate(2).unwrap());
+    }
+
+    #[test]
+    fn faces_with_normals() {
+        let content = "v 0 1 0
+v -1 0 0
+v 1 0 0
+
+vn -1 0 0
+vn 1 0 0
+vn 0 1 0
+
+vt 0 0
+
+f 1//3 2//1 3//2
+f 1/1/3 2/1/1 3/1/2";
+
+        let parser = ObjParser::parse(content);
+        let triangles = parser.default_group();
+        let expected = SmoothTriangle::new(
+            parser.vertex(1).unwrap(),
+            parser.vertex(2).unwrap(),
+            parser.vertex(3).unwrap(),
+            parser.normal(3).unwrap(),
+            parser.normal(1).unwrap(),
+            parser.normal(2).unwrap(),
+        )
+        .into_shape();
+
+        assert_eq!(0, parser.ignored_lines());
+        assert_eq!(expected, triangles[0]);
+        assert_eq!(expected, triangles[1]);
+    }
+
+    #[test]
+    fn faces_with_relative_indexes() {
+        let content = "v -1 1 0
+v -1 0 0
+v 1 0 0
+f -3 -2 -1";
+
+        let parser = ObjParser::parse(content);
+        assert_eq!(
+            Triangle::new(
+                parser.vertex(1).unwrap(),
+                parser.vertex(2).unwrap(),
+                parser.vertex(3).unwrap()
+            )
+            .into_shape(),
+            parser.default_group()[0]
+        );
+    }
+
+    #[test]
+    fn missing_elements_are_none() {
+        let parser = ObjParser::parse("v 1 2 3\nvn 0 0 1\nvt 0.5 0.5");
+        assert_eq!(None, parser.vertex(0));
+        assert_eq!(None, parser.vertex(2));
+        assert_eq!(None, parser.normal(0));
+        assert_eq!(None, parser.normal(2));
+        assert_eq!(None, parser.texture_coordinate(0));
+        assert_eq!(None, parser.texture_coordinate(2));
+    }
+
+    #[test]
+    fn repeated_group_names_add_to_the_same_group() {
+        let content = "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+
+g FirstGroup
+f 1 2 3
+g SecondGroup
+f 1 3 4
+g FirstGroup
+f 2 3 4";
+
+        let parser = ObjParser::parse(content);
+        assert_eq!(
+            vec!["default", "FirstGroup", "SecondGroup"],
+            parser.group_names()
+        );
+        assert_eq!(2, parser.group("FirstGroup").unwrap().len());
+        assert_eq!(1, parser.group("SecondGroup").unwrap().len());
+    }
+
+    #[test]
+    fn faces_referencing_missing_vertices_are_ignored() {
+        let content = "v -1 1 0
+v -1 0 0
+f 1 2 3";
+
+        let parser = ObjParser::parse(content);
+        assert_eq!(1, parser.ignored_lines());
+        assert!(parser.default_group().is_empty());
+    }
+}
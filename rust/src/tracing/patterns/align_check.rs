@@ -0,0 +1,90 @@
+use crate::display::color::Color;
+use crate::tracing::patterns::uv_pattern::UvPattern;
+use std::any::Any;
+
+/**
+ * A texture of one color with differently colored corners. Handy for checking that a mapping
+ * puts the texture the right way up and round.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignCheck {
+    main: Color,
+    upper_left: Color,
+    upper_right: Color,
+    bottom_left: Color,
+    bottom_right: Color,
+}
+
+impl AlignCheck {
+    pub fn new(
+        main: Color,
+        upper_left: Color,
+        upper_right: Color,
+        bottom_left: Color,
+        bottom_right: Color,
+    ) -> AlignCheck {
+        AlignCheck {
+            main,
+            upper_left,
+            upper_right,
+            bottom_left,
+            bottom_right,
+        }
+    }
+}
+
+impl UvPattern for AlignCheck {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn equals_uv_pattern(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|a| self == a)
+    }
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        if v > 0.8 {
+            if u < 0.2 {
+                return self.upper_left;
+            }
+            if u > 0.8 {
+                return self.upper_right;
+            }
+        } else if v < 0.2 {
+            if u < 0.2 {
+                return self.bottom_left;
+            }
+            if u > 0.8 {
+                return self.bottom_right;
+            }
+        }
+        self.main
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::tracing::patterns::align_check::AlignCheck;
+    use crate::tracing::patterns::uv_pattern::UvPattern;
+
+    #[test]
+    fn layout_of_align_check_pattern() {
+        let pattern = AlignCheck::new(
+            Color::WHITE,
+            Color::RED,
+            Color::MUSTARD_YELLOW,
+            Color::LIGHT_GREEN,
+            Color::LIGHT_BLUE,
+        );
+        let cases = vec![
+            (0.5, 0.5, Color::WHITE),
+            (0.1, 0.9, Color::RED),
+            (0.9, 0.9, Color::MUSTARD_YELLOW),
+            (0.1, 0.1, Color::LIGHT_GREEN),
+            (0.9, 0.1, Color::LIGHT_BLUE),
+        ];
+
+        for (u, v, expected) in cases {
+            assert_eq!(expected, pattern.uv_pattern_at(u, v));
+        }
+    }
+}
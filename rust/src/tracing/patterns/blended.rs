@@ -0,0 +1,85 @@
+use crate::display::color::Color;
+use crate::geometry::point::Point;
+use crate::tracing::patterns::pattern::{Pattern, PatternType, TransformedPattern};
+use std::any::Any;
+
+/**
+ * Two patterns (or colors) mixed together everywhere, e.g. stripes at right angles to each
+ * other blend into plaid. Weight is the share of the second pattern, from 0.0 to 1.0.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blended {
+    pattern_one: PatternType,
+    pattern_two: PatternType,
+    weight: f64,
+}
+
+impl Blended {
+    /// An even mix of the two.
+    pub fn new<A, B>(pattern_one: A, pattern_two: B) -> PatternType
+    where
+        A: Into<PatternType>,
+        B: Into<PatternType>,
+    {
+        Blended::weighted(pattern_one, pattern_two, 0.5)
+    }
+
+    pub fn weighted<A, B>(pattern_one: A, pattern_two: B, weight: f64) -> PatternType
+    where
+        A: Into<PatternType>,
+        B: Into<PatternType>,
+    {
+        TransformedPattern::using_identity(Box::new(Blended {
+            pattern_one: pattern_one.into(),
+            pattern_two: pattern_two.into(),
+            weight,
+        }))
+    }
+}
+
+impl Pattern for Blended {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn equals_pattern(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|a| self == a)
+    }
+    fn pattern_at(&self, point: Point) -> Color {
+        self.pattern_one.pattern_at(point) * (1.0 - self.weight)
+            + self.pattern_two.pattern_at(point) * self.weight
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::geometry::point::Point;
+    use crate::geometry::transformations::rotation_y;
+    use crate::tracing::patterns::blended::Blended;
+    use crate::tracing::patterns::pattern::Pattern;
+    use crate::tracing::patterns::stripe_pattern::StripePattern;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn blending_stripes_at_right_angles() {
+        let along_x = StripePattern::new(Color::WHITE, Color::BLACK);
+        let along_z =
+            StripePattern::new(Color::WHITE, Color::BLACK).with_transform(rotation_y(PI / 2.0));
+        let pattern = Blended::new(along_x, along_z);
+
+        let gray = Color::new(0.5, 0.5, 0.5);
+        assert_eq!(Color::WHITE, pattern.pattern_at(Point::at(0.5, 0.0, -0.5)));
+        assert_eq!(gray, pattern.pattern_at(Point::at(1.5, 0.0, -0.5)));
+        assert_eq!(gray, pattern.pattern_at(Point::at(0.5, 0.0, -1.5)));
+        assert_eq!(Color::BLACK, pattern.pattern_at(Point::at(1.5, 0.0, -1.5)));
+    }
+
+    #[test]
+    fn weighted_blend_favors_second_pattern() {
+        let pattern = Blended::weighted(Color::BLACK, Color::WHITE, 0.25);
+        assert_eq!(
+            Color::new(0.25, 0.25, 0.25),
+            pattern.pattern_at(Point::origin())
+        );
+    }
+}
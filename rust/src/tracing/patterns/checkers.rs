@@ -3,17 +3,21 @@ use crate::geometry::point::Point;
 use crate::tracing::patterns::pattern::{Pattern, PatternType, TransformedPattern};
 use std::any::Any;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Checkers {
-    color_one: Color,
-    color_two: Color,
+    pattern_one: PatternType,
+    pattern_two: PatternType,
 }
 
 impl Checkers {
-    pub fn new(color_one: Color, color_two: Color) -> PatternType {
+    pub fn new<A, B>(pattern_one: A, pattern_two: B) -> PatternType
+    where
+        A: Into<PatternType>,
+        B: Into<PatternType>,
+    {
         TransformedPattern::using_identity(Box::new(Checkers {
-            color_one,
-            color_two,
+            pattern_one: pattern_one.into(),
+            pattern_two: pattern_two.into(),
         }))
     }
 }
@@ -27,9 +31,9 @@ impl Pattern for Checkers {
     }
     fn pattern_at(&self, point: Point) -> Color {
         if (point.x.floor() + point.y.floor() + point.z.floor()) % 2.0 == 0.0 {
-            self.color_one
+            self.pattern_one.pattern_at(point)
         } else {
-            self.color_two
+            self.pattern_two.pattern_at(point)
         }
     }
 }
@@ -38,8 +42,10 @@ impl Pattern for Checkers {
 mod tests {
     use crate::display::color::Color;
     use crate::geometry::point::Point;
+    use crate::geometry::transformations::scaling;
     use crate::tracing::patterns::checkers::Checkers;
     use crate::tracing::patterns::pattern::Pattern;
+    use crate::tracing::patterns::stripe_pattern::StripePattern;
 
     #[test]
     fn checkers_repeat_in_x() {
@@ -64,4 +70,21 @@ mod tests {
         assert_eq!(Color::WHITE, pattern.pattern_at(Point::at(0.0, 0.0, 0.99)));
         assert_eq!(Color::BLACK, pattern.pattern_at(Point::at(0.0, 0.0, 1.01)));
     }
+
+    #[test]
+    fn checkers_of_nested_patterns() {
+        let stripes = StripePattern::new(Color::WHITE, Color::BLACK);
+        let scaled_stripes = StripePattern::new(Color::RED, Color::LIGHT_BLUE)
+            .with_transform(scaling(0.5, 1.0, 1.0));
+        let pattern = Checkers::new(stripes, scaled_stripes);
+
+        // The first square holds the plain stripes...
+        assert_eq!(Color::WHITE, pattern.pattern_at(Point::at(0.5, 0.5, 0.5)));
+        // ...the next the scaled ones, which change every half unit.
+        assert_eq!(Color::RED, pattern.pattern_at(Point::at(1.25, 0.5, 0.5)));
+        assert_eq!(
+            Color::LIGHT_BLUE,
+            pattern.pattern_at(Point::at(1.75, 0.5, 0.5))
+        );
+    }
 }
@@ -0,0 +1,112 @@
+use crate::display::color::Color;
+use crate::geometry::point::Point;
+use crate::helpers::noise;
+use crate::tracing::patterns::pattern::{Pattern, PatternType, TransformedPattern};
+use std::any::Any;
+use std::f64::consts::PI;
+
+/// How FractalNoise turns layered noise into a blend between its two colors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NoiseStyle {
+    /// Soft billows, as in clouds (fractal Brownian motion).
+    Clouds,
+    /// Sharp creases, as in fire or smoke (turbulence).
+    Turbulence,
+    /// Bands along x, twisted by turbulence into veins.
+    Marble,
+}
+
+/// A blend between two colors (or patterns) driven by several octaves of noise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FractalNoise {
+    pattern_one: PatternType,
+    pattern_two: PatternType,
+    style: NoiseStyle,
+    octaves: usize,
+}
+
+impl FractalNoise {
+    pub fn new<A, B>(
+        pattern_one: A,
+        pattern_two: B,
+        style: NoiseStyle,
+        octaves: usize,
+    ) -> PatternType
+    where
+        A: Into<PatternType>,
+        B: Into<PatternType>,
+    {
+        TransformedPattern::using_identity(Box::new(FractalNoise {
+            pattern_one: pattern_one.into(),
+            pattern_two: pattern_two.into(),
+            style,
+            octaves,
+        }))
+    }
+
+    // How far (0.0 to 1.0) from pattern_one to pattern_two at the point provided.
+    fn fraction_at(&self, point: Point) -> f64 {
+        let fraction = match self.style {
+            NoiseStyle::Clouds => (noise::fractal_brownian_motion(point, self.octaves) + 1.0) / 2.0,
+            NoiseStyle::Turbulence => noise::turbulence(point, self.octaves),
+            NoiseStyle::Marble => {
+                let twist = 5.0 * noise::turbulence(point, self.octaves);
+                (((point.x + twist) * PI).sin() + 1.0) / 2.0
+            }
+        };
+        fraction.clamp(0.0, 1.0)
+    }
+}
+
+impl Pattern for FractalNoise {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn equals_pattern(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|a| self == a)
+    }
+    fn pattern_at(&self, point: Point) -> Color {
+        let color_one = self.pattern_one.pattern_at(point);
+        let color_two = self.pattern_two.pattern_at(point);
+        color_one + (color_two - color_one) * self.fraction_at(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::geometry::point::Point;
+    use crate::tracing::patterns::fractal_noise::{FractalNoise, NoiseStyle};
+    use crate::tracing::patterns::pattern::Pattern;
+
+    #[test]
+    fn noise_patterns_blend_between_their_colors() {
+        let styles = vec![
+            NoiseStyle::Clouds,
+            NoiseStyle::Turbulence,
+            NoiseStyle::Marble,
+        ];
+        for style in styles {
+            let pattern = FractalNoise::new(Color::BLACK, Color::WHITE, style, 4);
+            let mut shades = vec![];
+            for step in 0..50 {
+                let point = Point::at(step as f64 * 0.37, step as f64 * 0.11, -0.5);
+                let color = pattern.pattern_at(point);
+                assert!(color.max_channel() >= 0.0 && color.max_channel() <= 1.0);
+                shades.push(color.max_channel());
+            }
+            let lightest = shades.iter().cloned().fold(0.0, f64::max);
+            let darkest = shades.iter().cloned().fold(1.0, f64::min);
+            assert!(lightest - darkest > 0.1, "{:?} is too uniform", style);
+        }
+    }
+
+    #[test]
+    fn clouds_are_even_blend_on_lattice_points() {
+        let pattern = FractalNoise::new(Color::BLACK, Color::WHITE, NoiseStyle::Clouds, 3);
+        assert_eq!(
+            Color::new(0.5, 0.5, 0.5),
+            pattern.pattern_at(Point::at(1, 2, 3))
+        );
+    }
+}
@@ -3,17 +3,21 @@ use crate::geometry::point::Point;
 use crate::tracing::patterns::pattern::{Pattern, PatternType, TransformedPattern};
 use std::any::Any;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Gradient {
-    color_one: Color,
-    color_two: Color,
+    pattern_one: PatternType,
+    pattern_two: PatternType,
 }
 
 impl Gradient {
-    pub fn new(color_one: Color, color_two: Color) -> PatternType {
+    pub fn new<A, B>(pattern_one: A, pattern_two: B) -> PatternType
+    where
+        A: Into<PatternType>,
+        B: Into<PatternType>,
+    {
         TransformedPattern::using_identity(Box::new(Gradient {
-            color_one,
-            color_two,
+            pattern_one: pattern_one.into(),
+            pattern_two: pattern_two.into(),
         }))
     }
 }
@@ -26,9 +30,10 @@ impl Pattern for Gradient {
         other.downcast_ref::<Self>().map_or(false, |a| self == a)
     }
     fn pattern_at(&self, point: Point) -> Color {
-        let distance = self.color_two - self.color_one;
+        let color_one = self.pattern_one.pattern_at(point);
+        let distance = self.pattern_two.pattern_at(point) - color_one;
         let fraction = (point.x.abs() - point.x.abs().floor()) * point.x.signum();
-        self.color_one + distance * fraction
+        color_one + distance * fraction
     }
 }
 
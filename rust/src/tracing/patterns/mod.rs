@@ -1,6 +1,15 @@
+pub mod align_check;
+pub mod blended;
 pub mod checkers;
+pub mod fractal_noise;
 pub mod gradient;
 pub mod pattern;
+pub mod perturbed;
 pub mod rings;
 pub mod solid;
 pub mod stripe_pattern;
+pub mod texture_map;
+pub mod uv_checkers;
+pub mod uv_image;
+pub mod uv_mapping;
+pub mod uv_pattern;
@@ -4,6 +4,10 @@ use crate::geometry::point::Point;
 use std::any::Any;
 use std::fmt::Debug;
 
+/**
+ * A pattern, with its own transform. Compound patterns (stripes, checkers, etc.) take their
+ * parts as `Into<PatternType>`, so each may be a plain Color or another pattern.
+ */
 pub type PatternType = TransformedPattern;
 
 impl PartialEq for PatternType {
@@ -91,13 +95,12 @@ mod tests {
     use crate::geometry::transformations;
     use crate::geometry::vector::Vector;
     use crate::tracing::material::Material;
-    use crate::tracing::patterns::pattern::{Pattern};
+    use crate::tracing::patterns::pattern::Pattern;
     use crate::tracing::patterns::stripe_pattern::StripePattern;
     use crate::tracing::point_light::PointLight;
     use crate::tracing::shapes::shape::Shape;
-    
+
     use crate::tracing::test_helpers::TestPattern;
-    
 
     // high level tests copied from original StripedPattern
 
@@ -114,7 +117,7 @@ mod tests {
             Point::at(1.5, 0.0, 0.0),
             Vector::new(10, -10, 10),
             Vector::new(0, 0, 0),
-            true,
+            0.0,
         );
         assert_eq!(Color::WHITE, color)
     }
@@ -132,7 +135,7 @@ mod tests {
             Point::at(1.5, 0.0, 0.0),
             Vector::new(10, -10, 10),
             Vector::new(0, 0, 0),
-            true,
+            0.0,
         );
         assert_eq!(Color::WHITE, color)
     }
@@ -151,7 +154,7 @@ mod tests {
             Point::at(1.5, 0.0, 0.0),
             Vector::new(10, -10, 10),
             Vector::new(0, 0, 0),
-            true,
+            0.0,
         );
         assert_eq!(Color::WHITE, color)
     }
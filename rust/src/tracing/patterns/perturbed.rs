@@ -0,0 +1,81 @@
+use crate::display::color::Color;
+use crate::geometry::point::Point;
+use crate::helpers::noise;
+use crate::tracing::patterns::pattern::{Pattern, PatternType, TransformedPattern};
+use std::any::Any;
+
+/**
+ * Another pattern, with the points it sees jittered by noise so its edges wobble.
+ * Perturbing Rings gives wood grain, for example. Scale is how far points may move.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Perturbed {
+    pattern: PatternType,
+    scale: f64,
+}
+
+impl Perturbed {
+    pub fn new(pattern: PatternType, scale: f64) -> PatternType {
+        TransformedPattern::using_identity(Box::new(Perturbed { pattern, scale }))
+    }
+}
+
+impl Pattern for Perturbed {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn equals_pattern(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|a| self == a)
+    }
+    fn pattern_at(&self, point: Point) -> Color {
+        self.pattern
+            .pattern_at(point + noise::perlin_vector(point) * self.scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::geometry::point::Point;
+    use crate::geometry::transformations::scaling;
+    use crate::tracing::patterns::pattern::Pattern;
+    use crate::tracing::patterns::perturbed::Perturbed;
+    use crate::tracing::patterns::stripe_pattern::StripePattern;
+    use crate::tracing::test_helpers::TestPattern;
+
+    #[test]
+    fn perturbing_by_nothing_changes_nothing() {
+        let pattern = Perturbed::new(StripePattern::new(Color::WHITE, Color::BLACK), 0.0);
+        let stripes = StripePattern::new(Color::WHITE, Color::BLACK);
+
+        for x in 0..20 {
+            let point = Point::at(x as f64 * 0.23, 0.4, 0.1);
+            assert_eq!(stripes.pattern_at(point), pattern.pattern_at(point));
+        }
+    }
+
+    #[test]
+    fn perturbing_moves_points_up_to_scale() {
+        let pattern = Perturbed::new(TestPattern {}.without_transform(), 0.5);
+
+        let mut moved = false;
+        for x in 0..20 {
+            let point = Point::at(x as f64 * 0.23, 0.4, 0.1);
+            let seen = pattern.pattern_at(point);
+            let expected = Color::new(point.x, point.y, point.z);
+            assert!(seen.difference(&expected) <= 0.5);
+            moved |= seen != expected;
+        }
+        assert!(moved);
+    }
+
+    #[test]
+    fn perturbed_pattern_keeps_its_own_transform() {
+        let inner = TestPattern {}.with_transform(scaling(2, 2, 2));
+        let pattern = Perturbed::new(inner, 0.0);
+        assert_eq!(
+            Color::new(1.0, 1.5, 2.0),
+            pattern.pattern_at(Point::at(2, 3, 4))
+        );
+    }
+}
@@ -3,17 +3,21 @@ use crate::geometry::point::Point;
 use crate::tracing::patterns::pattern::{Pattern, PatternType, TransformedPattern};
 use std::any::Any;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Rings {
-    color_one: Color,
-    color_two: Color,
+    pattern_one: PatternType,
+    pattern_two: PatternType,
 }
 
 impl Rings {
-    pub fn new(color_one: Color, color_two: Color) -> PatternType {
+    pub fn new<A, B>(pattern_one: A, pattern_two: B) -> PatternType
+    where
+        A: Into<PatternType>,
+        B: Into<PatternType>,
+    {
         TransformedPattern::using_identity(Box::new(Rings {
-            color_one,
-            color_two,
+            pattern_one: pattern_one.into(),
+            pattern_two: pattern_two.into(),
         }))
     }
 }
@@ -27,9 +31,9 @@ impl Pattern for Rings {
     }
     fn pattern_at(&self, point: Point) -> Color {
         if (point.x * point.x + point.z * point.z).sqrt().floor() % 2.0 == 0.0 {
-            self.color_one
+            self.pattern_one.pattern_at(point)
         } else {
-            self.color_two
+            self.pattern_two.pattern_at(point)
         }
     }
 }
@@ -14,6 +14,13 @@ impl Solid {
     }
 }
 
+/// Lets compound patterns take plain colors wherever they take a sub-pattern.
+impl From<Color> for PatternType {
+    fn from(color: Color) -> Self {
+        Solid::new(color)
+    }
+}
+
 impl Pattern for Solid {
     fn as_any(&self) -> &dyn Any {
         self
@@ -31,7 +38,7 @@ mod tests {
     use crate::display::color::Color;
     use crate::geometry::point::Point;
     use crate::tracing::patterns::pattern::Pattern;
-    
+
     use crate::tracing::patterns::solid::Solid;
 
     #[test]
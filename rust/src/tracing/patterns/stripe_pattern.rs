@@ -3,15 +3,22 @@ use crate::geometry::point::Point;
 use crate::tracing::patterns::pattern::{Pattern, PatternType, TransformedPattern};
 use std::any::Any;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StripePattern {
-    color_a: Color,
-    color_b: Color,
+    pattern_a: PatternType,
+    pattern_b: PatternType,
 }
 
 impl StripePattern {
-    pub fn new(color_a: Color, color_b: Color) -> PatternType {
-        TransformedPattern::using_identity(Box::new(StripePattern { color_a, color_b }))
+    pub fn new<A, B>(pattern_a: A, pattern_b: B) -> PatternType
+    where
+        A: Into<PatternType>,
+        B: Into<PatternType>,
+    {
+        TransformedPattern::using_identity(Box::new(StripePattern {
+            pattern_a: pattern_a.into(),
+            pattern_b: pattern_b.into(),
+        }))
     }
 }
 
@@ -23,12 +30,13 @@ impl Pattern for StripePattern {
         other.downcast_ref::<Self>().map_or(false, |a| self == a)
     }
     fn pattern_at(&self, point: Point) -> Color {
-        match point.x % 2.0 {
-            x if (0.0..1.0).contains(&x) => self.color_a,
-            x if x >= 1.0 => self.color_b,
-            x if (-1.0..0.0).contains(&x) => self.color_b,
-            _ => self.color_a,
-        }
+        let pattern = match point.x % 2.0 {
+            x if (0.0..1.0).contains(&x) => &self.pattern_a,
+            x if x >= 1.0 => &self.pattern_b,
+            x if (-1.0..0.0).contains(&x) => &self.pattern_b,
+            _ => &self.pattern_a,
+        };
+        pattern.pattern_at(point)
     }
 }
 
@@ -36,13 +44,9 @@ impl Pattern for StripePattern {
 mod tests {
     use crate::display::color::Color;
     use crate::geometry::point::Point;
-    
-    
-    
+
     use crate::tracing::patterns::pattern::Pattern;
     use crate::tracing::patterns::stripe_pattern::StripePattern;
-    
-    
 
     #[test]
     fn stripe_pattern_is_constant_in_y() {
@@ -0,0 +1,129 @@
+use crate::display::color::Color;
+use crate::geometry::point::Point;
+use crate::tracing::patterns::pattern::{Pattern, PatternType, TransformedPattern};
+use crate::tracing::patterns::uv_mapping::UvMapping;
+use crate::tracing::patterns::uv_pattern::UvPattern;
+use std::any::Any;
+
+/// A 2D UvPattern wrapped onto surfaces with a UvMapping.
+#[derive(Debug, Clone)]
+pub struct TextureMapPattern {
+    uv_pattern: Box<dyn UvPattern>,
+    mapping: UvMapping,
+}
+
+impl TextureMapPattern {
+    pub fn new<P: UvPattern + 'static>(uv_pattern: P, mapping: UvMapping) -> PatternType {
+        TransformedPattern::using_identity(Box::new(TextureMapPattern {
+            uv_pattern: Box::new(uv_pattern),
+            mapping,
+        }))
+    }
+}
+
+impl PartialEq for TextureMapPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.uv_pattern.as_ref() == other.uv_pattern.as_ref() && self.mapping == other.mapping
+    }
+}
+
+impl Pattern for TextureMapPattern {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn equals_pattern(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|a| self == a)
+    }
+    fn pattern_at(&self, point: Point) -> Color {
+        let (u, v) = self.mapping.map(point);
+        self.uv_pattern.uv_pattern_at(u, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::canvas::Canvas;
+    use crate::display::color::Color;
+    use crate::display::resolution::Resolution;
+    use crate::geometry::point::Point;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::material::Material;
+    use crate::tracing::patterns::align_check::AlignCheck;
+    use crate::tracing::patterns::pattern::Pattern;
+    use crate::tracing::patterns::texture_map::TextureMapPattern;
+    use crate::tracing::patterns::uv_checkers::UvCheckers;
+    use crate::tracing::patterns::uv_image::UvImage;
+    use crate::tracing::patterns::uv_mapping::UvMapping;
+    use crate::tracing::point_light::PointLight;
+    use crate::tracing::ray::Ray;
+    use crate::tracing::shapes::shape::Shape;
+    use crate::tracing::world::World;
+
+    #[test]
+    fn texture_map_with_spherical_mapping() {
+        let checkers = UvCheckers::new(16, 8, Color::BLACK, Color::WHITE);
+        let pattern = TextureMapPattern::new(checkers, UvMapping::Spherical);
+        let cases = vec![
+            (Point::at(0.4315, 0.4670, 0.7719), Color::WHITE),
+            (Point::at(-0.9654, 0.2552, -0.0534), Color::BLACK),
+            (Point::at(0.1039, 0.7090, 0.6975), Color::WHITE),
+            (Point::at(-0.4986, -0.7856, -0.3663), Color::BLACK),
+            (Point::at(-0.0317, -0.9395, 0.3411), Color::BLACK),
+            (Point::at(0.4809, -0.7721, 0.4154), Color::BLACK),
+            (Point::at(0.0285, -0.9612, -0.2745), Color::BLACK),
+            (Point::at(-0.5734, -0.2162, -0.7903), Color::WHITE),
+            (Point::at(0.7688, -0.1470, 0.6223), Color::BLACK),
+            (Point::at(-0.7652, 0.2175, 0.6060), Color::BLACK),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected, pattern.pattern_at(point));
+        }
+    }
+
+    #[test]
+    fn texture_map_with_cubic_mapping() {
+        let align_check = AlignCheck::new(
+            Color::WHITE,
+            Color::RED,
+            Color::MUSTARD_YELLOW,
+            Color::LIGHT_GREEN,
+            Color::LIGHT_BLUE,
+        );
+        let pattern = TextureMapPattern::new(align_check, UvMapping::Cubic);
+
+        // The corners of the front face.
+        let cases = vec![
+            (Point::at(0, 0, 1), Color::WHITE),
+            (Point::at(-0.9, 0.9, 1.0), Color::RED),
+            (Point::at(0.9, 0.9, 1.0), Color::MUSTARD_YELLOW),
+            (Point::at(-0.9, -0.9, 1.0), Color::LIGHT_GREEN),
+            (Point::at(0.9, -0.9, 1.0), Color::LIGHT_BLUE),
+        ];
+        for (point, expected) in cases {
+            assert_eq!(expected, pattern.pattern_at(point));
+        }
+    }
+
+    #[test]
+    fn shading_shape_textured_with_large_image() {
+        let mut image = Canvas::new(Resolution::new(1024, 1024));
+        for (x, y) in Resolution::new(1024, 1024).coordinates() {
+            image.write_pixel(x, y, Color::RED);
+        }
+        let pattern = TextureMapPattern::new(UvImage::new(image), UvMapping::Spherical);
+        let material = Material::default()
+            .with_pattern(pattern)
+            .with_ambient(1.0)
+            .with_diffuse(0.0)
+            .with_specular(0.0);
+        let shape = Shape::sphere().with_material(material);
+
+        // Copies share the image, so compare without visiting a million pixels.
+        assert_eq!(shape, shape.clone());
+
+        let world = World::new(vec![shape], PointLight::default());
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+        assert_eq!(Color::RED, world.color_at(&ray));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::display::color::Color;
+use crate::tracing::patterns::uv_pattern::UvPattern;
+use std::any::Any;
+
+/// A checkerboard of width by height squares covering the texture.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UvCheckers {
+    width: f64,
+    height: f64,
+    color_one: Color,
+    color_two: Color,
+}
+
+impl UvCheckers {
+    pub fn new(width: usize, height: usize, color_one: Color, color_two: Color) -> UvCheckers {
+        UvCheckers {
+            width: width as f64,
+            height: height as f64,
+            color_one,
+            color_two,
+        }
+    }
+}
+
+impl UvPattern for UvCheckers {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn equals_uv_pattern(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|a| self == a)
+    }
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        let u = (u * self.width).floor();
+        let v = (v * self.height).floor();
+
+        if (u + v) % 2.0 == 0.0 {
+            self.color_one
+        } else {
+            self.color_two
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::tracing::patterns::uv_checkers::UvCheckers;
+    use crate::tracing::patterns::uv_pattern::UvPattern;
+
+    #[test]
+    fn checker_pattern_in_2d() {
+        let checkers = UvCheckers::new(2, 2, Color::BLACK, Color::WHITE);
+        let cases = vec![
+            (0.0, 0.0, Color::BLACK),
+            (0.5, 0.0, Color::WHITE),
+            (0.0, 0.5, Color::WHITE),
+            (0.5, 0.5, Color::BLACK),
+            (1.0, 1.0, Color::BLACK),
+        ];
+
+        for (u, v, expected) in cases {
+            assert_eq!(expected, checkers.uv_pattern_at(u, v));
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::display::canvas::Canvas;
+use crate::display::color::Color;
+use crate::display::image_reader;
+use crate::tracing::patterns::uv_pattern::UvPattern;
+use image::ImageResult;
+use std::any::Any;
+use std::sync::Arc;
+
+/// How the color between the centers of an image's pixels is found.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextureFilter {
+    /// The color of whichever pixel the point falls in. Blocky when magnified.
+    Nearest,
+    /// A blend of the four nearest pixels. Smooth when magnified.
+    Bilinear,
+}
+
+/// What lies beyond the edges of an image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextureAddressing {
+    /// The image repeats, so it can tile a surface.
+    Wrap,
+    /// The edge pixels stretch on forever.
+    Clamp,
+}
+
+/// An image (e.g. a photograph or logo) as a texture, for wrapping onto surfaces by TextureMapPattern.
+#[derive(Debug, Clone)]
+pub struct UvImage {
+    // Shared, so copies of a material don't copy every pixel.
+    image: Arc<Canvas>,
+    filter: TextureFilter,
+    addressing: TextureAddressing,
+}
+
+impl UvImage {
+    pub fn new(image: Canvas) -> UvImage {
+        UvImage {
+            image: Arc::new(image),
+            filter: TextureFilter::Nearest,
+            addressing: TextureAddressing::Wrap,
+        }
+    }
+
+    /// Load any image file the image crate can read (PNG, JPEG, etc.).
+    pub fn load(file_name: &str) -> ImageResult<UvImage> {
+        Ok(UvImage::new(image_reader::read_image(file_name)?))
+    }
+
+    pub fn with_filter(self, filter: TextureFilter) -> UvImage {
+        UvImage { filter, ..self }
+    }
+
+    pub fn with_addressing(self, addressing: TextureAddressing) -> UvImage {
+        UvImage { addressing, ..self }
+    }
+
+    // The color of the pixel provided, applying the addressing mode to those beyond the edges.
+    fn pixel_at(&self, x: i64, y: i64) -> Color {
+        let (width, height) = (self.image.width as i64, self.image.height as i64);
+        let (x, y) = match self.addressing {
+            TextureAddressing::Wrap => (x.rem_euclid(width), y.rem_euclid(height)),
+            TextureAddressing::Clamp => (x.clamp(0, width - 1), y.clamp(0, height - 1)),
+        };
+        self.image.pixel_at(x as usize, y as usize)
+    }
+}
+
+impl PartialEq for UvImage {
+    fn eq(&self, other: &Self) -> bool {
+        // The same image, not just the same pixels: comparing every pixel is far too slow.
+        Arc::ptr_eq(&self.image, &other.image)
+            && self.filter == other.filter
+            && self.addressing == other.addressing
+    }
+}
+
+impl UvPattern for UvImage {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn equals_uv_pattern(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|a| self == a)
+    }
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        // Image rows run top to bottom, whereas v increases upward.
+        let x = u * self.image.width as f64;
+        let y = (1.0 - v) * self.image.height as f64;
+
+        match self.filter {
+            TextureFilter::Nearest => self.pixel_at(x.floor() as i64, y.floor() as i64),
+            TextureFilter::Bilinear => {
+                // Measured from the centers of the pixels.
+                let (x, y) = (x - 0.5, y - 0.5);
+                let (left, top) = (x.floor(), y.floor());
+                let (x_fraction, y_fraction) = (x - left, y - top);
+                let (left, top) = (left as i64, top as i64);
+
+                let upper = self.pixel_at(left, top) * (1.0 - x_fraction)
+                    + self.pixel_at(left + 1, top) * x_fraction;
+                let lower = self.pixel_at(left, top + 1) * (1.0 - x_fraction)
+                    + self.pixel_at(left + 1, top + 1) * x_fraction;
+                upper * (1.0 - y_fraction) + lower * y_fraction
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::canvas::Canvas;
+    use crate::display::color::Color;
+    use crate::display::resolution::Resolution;
+    use crate::tracing::patterns::uv_image::{TextureAddressing, TextureFilter, UvImage};
+    use crate::tracing::patterns::uv_pattern::UvPattern;
+
+    // A 2x2 image: black and white along the top, red and blue along the bottom.
+    fn test_image() -> Canvas {
+        let mut canvas = Canvas::new(Resolution::new(2, 2));
+        canvas.write_pixel(1, 0, Color::WHITE);
+        canvas.write_pixel(0, 1, Color::new(1, 0, 0));
+        canvas.write_pixel(1, 1, Color::new(0, 0, 1));
+        canvas
+    }
+
+    #[test]
+    fn images_are_equal_only_to_copies_of_themselves() {
+        let image = UvImage::new(test_image());
+        assert_eq!(image, image.clone());
+        assert_ne!(image, image.clone().with_filter(TextureFilter::Bilinear));
+        // Comparing the pixels themselves would be far too slow on every hit.
+        assert_ne!(image, UvImage::new(test_image()));
+    }
+
+    #[test]
+    fn nearest_filtering_picks_the_pixel() {
+        let image = UvImage::new(test_image());
+        let cases = vec![
+            (0.1, 0.9, Color::BLACK),
+            (0.9, 0.9, Color::WHITE),
+            (0.1, 0.1, Color::new(1, 0, 0)),
+            (0.9, 0.1, Color::new(0, 0, 1)),
+        ];
+
+        for (u, v, expected) in cases {
+            assert_eq!(expected, image.uv_pattern_at(u, v));
+        }
+    }
+
+    #[test]
+    fn bilinear_filtering_blends_neighboring_pixels() {
+        let image = UvImage::new(test_image())
+            .with_filter(TextureFilter::Bilinear)
+            .with_addressing(TextureAddressing::Clamp);
+
+        // At a pixel's center, only that pixel counts.
+        assert_eq!(Color::WHITE, image.uv_pattern_at(0.75, 0.75));
+        // Midway between all four.
+        assert_eq!(Color::new(0.5, 0.25, 0.5), image.uv_pattern_at(0.5, 0.5));
+        // Midway along the top.
+        assert_eq!(Color::new(0.5, 0.5, 0.5), image.uv_pattern_at(0.5, 0.75));
+    }
+
+    #[test]
+    fn addressing_beyond_the_edges() {
+        let wrapped = UvImage::new(test_image());
+        let clamped = UvImage::new(test_image()).with_addressing(TextureAddressing::Clamp);
+
+        // Just past the right edge of the top row.
+        assert_eq!(Color::BLACK, wrapped.uv_pattern_at(1.1, 0.9));
+        assert_eq!(Color::WHITE, clamped.uv_pattern_at(1.1, 0.9));
+
+        // Well below the bottom.
+        assert_eq!(Color::BLACK, wrapped.uv_pattern_at(0.1, -0.1));
+        assert_eq!(Color::new(1, 0, 0), clamped.uv_pattern_at(0.1, -3.0));
+    }
+
+    #[test]
+    fn bilinear_filtering_wraps_across_edges() {
+        let image = UvImage::new(test_image()).with_filter(TextureFilter::Bilinear);
+        // Midway between the right and (wrapped round) left edges of the top row.
+        assert_eq!(Color::new(0.5, 0.5, 0.5), image.uv_pattern_at(1.0, 0.75));
+    }
+}
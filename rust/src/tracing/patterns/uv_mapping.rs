@@ -0,0 +1,181 @@
+use crate::geometry::point::Point;
+use std::f64::consts::PI;
+
+/// How points on (the object space surface of) a Shape are unwrapped onto a 2D texture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UvMapping {
+    /// Longitude and latitude around the unit sphere.
+    Spherical,
+    /// The x/z plane, repeating every unit.
+    Planar,
+    /// Around the y axis, repeating every unit of height.
+    Cylindrical,
+    /// Each face of the unit cube gets the whole texture.
+    Cubic,
+}
+
+impl UvMapping {
+    /// The texture coordinates of the point, each in the range 0.0 to 1.0 (v increasing upward).
+    pub fn map(&self, point: Point) -> (f64, f64) {
+        match self {
+            UvMapping::Spherical => spherical_map(point),
+            UvMapping::Planar => planar_map(point),
+            UvMapping::Cylindrical => cylindrical_map(point),
+            UvMapping::Cubic => cubic_map(point),
+        }
+    }
+}
+
+// The fraction of the way around the y axis, starting (and ending) at -z, heading toward +x.
+fn around_y_axis(point: Point) -> f64 {
+    let theta = point.x.atan2(point.z);
+    let raw_u = theta / (2.0 * PI);
+    1.0 - (raw_u + 0.5)
+}
+
+fn spherical_map(point: Point) -> (f64, f64) {
+    let radius = (point.x * point.x + point.y * point.y + point.z * point.z).sqrt();
+    let phi = (point.y / radius).acos();
+    (around_y_axis(point), 1.0 - phi / PI)
+}
+
+fn planar_map(point: Point) -> (f64, f64) {
+    (point.x.rem_euclid(1.0), point.z.rem_euclid(1.0))
+}
+
+fn cylindrical_map(point: Point) -> (f64, f64) {
+    (around_y_axis(point), point.y.rem_euclid(1.0))
+}
+
+// Each face is seen from outside the cube, upright, except up and down (seen from the front).
+fn cubic_map(point: Point) -> (f64, f64) {
+    let (x, y, z) = (point.x, point.y, point.z);
+    let largest = x.abs().max(y.abs()).max(z.abs());
+    let unit = |value: f64| value.rem_euclid(2.0) / 2.0;
+
+    if largest == x {
+        // right
+        (unit(1.0 - z), unit(y + 1.0))
+    } else if largest == -x {
+        // left
+        (unit(z + 1.0), unit(y + 1.0))
+    } else if largest == y {
+        // up
+        (unit(x + 1.0), unit(1.0 - z))
+    } else if largest == -y {
+        // down
+        (unit(x + 1.0), unit(z + 1.0))
+    } else if largest == z {
+        // front
+        (unit(x + 1.0), unit(y + 1.0))
+    } else {
+        // back
+        (unit(1.0 - x), unit(y + 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::point::Point;
+    use crate::helpers::approximate::Approximate;
+    use crate::tracing::patterns::uv_mapping::UvMapping;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    fn check_mapping(mapping: UvMapping, cases: Vec<(Point, f64, f64)>) {
+        for (point, u, v) in cases {
+            let (actual_u, actual_v) = mapping.map(point);
+            assert!(
+                u.almost(actual_u),
+                "u of {:?}: {} != {}",
+                point,
+                u,
+                actual_u
+            );
+            assert!(
+                v.almost(actual_v),
+                "v of {:?}: {} != {}",
+                point,
+                v,
+                actual_v
+            );
+        }
+    }
+
+    #[test]
+    fn spherical_mapping_on_3d_point() {
+        check_mapping(
+            UvMapping::Spherical,
+            vec![
+                (Point::at(0, 0, -1), 0.0, 0.5),
+                (Point::at(1, 0, 0), 0.25, 0.5),
+                (Point::at(0, 0, 1), 0.5, 0.5),
+                (Point::at(-1, 0, 0), 0.75, 0.5),
+                (Point::at(0, 1, 0), 0.5, 1.0),
+                (Point::at(0, -1, 0), 0.5, 0.0),
+                (Point::at(FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0), 0.25, 0.75),
+            ],
+        );
+    }
+
+    #[test]
+    fn planar_mapping_on_3d_point() {
+        check_mapping(
+            UvMapping::Planar,
+            vec![
+                (Point::at(0.25, 0.0, 0.5), 0.25, 0.5),
+                (Point::at(0.25, 0.0, -0.25), 0.25, 0.75),
+                (Point::at(0.25, 0.5, -0.25), 0.25, 0.75),
+                (Point::at(1.25, 0.0, 0.5), 0.25, 0.5),
+                (Point::at(0.25, 0.0, -1.75), 0.25, 0.25),
+                (Point::at(1, 0, -1), 0.0, 0.0),
+                (Point::at(0, 0, 0), 0.0, 0.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn cylindrical_mapping_on_3d_point() {
+        check_mapping(
+            UvMapping::Cylindrical,
+            vec![
+                (Point::at(0, 0, -1), 0.0, 0.0),
+                (Point::at(0.0, 0.5, -1.0), 0.0, 0.5),
+                (Point::at(0, 1, -1), 0.0, 0.0),
+                (Point::at(FRAC_1_SQRT_2, 0.5, -FRAC_1_SQRT_2), 0.125, 0.5),
+                (Point::at(1.0, 0.5, 0.0), 0.25, 0.5),
+                (Point::at(FRAC_1_SQRT_2, 0.5, FRAC_1_SQRT_2), 0.375, 0.5),
+                (Point::at(0.0, -0.25, 1.0), 0.5, 0.75),
+                (Point::at(-FRAC_1_SQRT_2, 0.5, FRAC_1_SQRT_2), 0.625, 0.5),
+                (Point::at(-1.0, 1.25, 0.0), 0.75, 0.25),
+                (Point::at(-FRAC_1_SQRT_2, 0.5, -FRAC_1_SQRT_2), 0.875, 0.5),
+            ],
+        );
+    }
+
+    #[test]
+    fn cubic_mapping_on_each_face() {
+        check_mapping(
+            UvMapping::Cubic,
+            vec![
+                // front
+                (Point::at(-0.5, 0.5, 1.0), 0.25, 0.75),
+                (Point::at(0.5, -0.5, 1.0), 0.75, 0.25),
+                // back
+                (Point::at(0.5, 0.5, -1.0), 0.25, 0.75),
+                (Point::at(-0.5, -0.5, -1.0), 0.75, 0.25),
+                // left
+                (Point::at(-1.0, 0.5, -0.5), 0.25, 0.75),
+                (Point::at(-1.0, -0.5, 0.5), 0.75, 0.25),
+                // right
+                (Point::at(1.0, 0.5, 0.5), 0.25, 0.75),
+                (Point::at(1.0, -0.5, -0.5), 0.75, 0.25),
+                // up
+                (Point::at(-0.5, 1.0, -0.5), 0.25, 0.75),
+                (Point::at(0.5, 1.0, 0.5), 0.75, 0.25),
+                // down
+                (Point::at(-0.5, -1.0, 0.5), 0.25, 0.75),
+                (Point::at(0.5, -1.0, -0.5), 0.75, 0.25),
+            ],
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use crate::display::color::Color;
+use std::any::Any;
+use std::fmt::Debug;
+
+/// UvPattern is a Strategy coloring a 2D texture, for wrapping onto surfaces by TextureMapPattern.
+pub trait UvPattern: UvPatternClone + Any + Send + Sync + Debug {
+    fn as_any(&self) -> &dyn Any;
+    fn equals_uv_pattern(&self, other: &dyn Any) -> bool;
+    /// The color at the texture coordinates provided, each from 0.0 to 1.0 (v increasing upward).
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color;
+}
+
+impl PartialEq for dyn UvPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.equals_uv_pattern(other.as_any())
+    }
+}
+
+pub trait UvPatternClone {
+    fn clone_box(&self) -> Box<dyn UvPattern>;
+}
+
+impl<T> UvPatternClone for T
+where
+    T: 'static + UvPattern + Clone,
+{
+    fn clone_box(&self) -> Box<dyn UvPattern> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn UvPattern> {
+    fn clone(&self) -> Box<dyn UvPattern> {
+        self.clone_box()
+    }
+}
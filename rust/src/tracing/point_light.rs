@@ -1,7 +1,8 @@
 use crate::display::color::Color;
 use crate::geometry::point::Point;
+use crate::tracing::light::{Light, LightSample};
 
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Eq, PartialEq, Debug, Clone)]
 pub struct PointLight {
     position: Point,
     intensity: Color,
@@ -19,17 +20,19 @@ impl PointLight {
         PointLight::new(Point::at(-10, 10, -10), Color::WHITE)
     }
 
-    pub fn black_light() -> PointLight {
-        PointLight::new(Point::origin(), Color::BLACK)
-    }
-
     pub fn position(&self) -> Point {
         self.position
     }
+}
 
-    pub fn intensity(&self) -> Color {
+impl Light for PointLight {
+    fn intensity(&self) -> Color {
         self.intensity
     }
+
+    fn samples_from(&self, point: Point) -> Vec<LightSample> {
+        vec![LightSample::between(point, self.position)]
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,61 @@
+use crate::display::resolution::Resolution;
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::tracing::projections::projection::Projection;
+use crate::tracing::ray::Ray;
+use std::f64::consts::PI;
+
+/**
+ * A full 360° view: longitude runs across the image and latitude down it, as used for
+ * panoramas and VR previews. Images should be twice as wide as they are tall.
+ */
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Equirectangular {}
+
+impl Equirectangular {
+    pub fn new() -> Equirectangular {
+        Equirectangular {}
+    }
+}
+
+impl Projection for Equirectangular {
+    fn ray_for(&self, resolution: Resolution, x: f64, y: f64) -> Option<Ray> {
+        // Straight ahead is the center of the image, with +x to the left as for other projections.
+        let longitude = (0.5 - x / resolution.hsize() as f64) * 2.0 * PI;
+        let latitude = (0.5 - y / resolution.vsize() as f64) * PI;
+
+        let direction = Vector::new(
+            longitude.sin() * latitude.cos(),
+            latitude.sin(),
+            -longitude.cos() * latitude.cos(),
+        );
+        Some(Ray::new(Point::origin(), direction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::resolution::Resolution;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::projections::equirectangular::Equirectangular;
+    use crate::tracing::projections::projection::Projection;
+
+    #[test]
+    fn equirectangular_covers_every_direction() {
+        let projection = Equirectangular::new();
+        let resolution = Resolution::new(360, 180);
+        let cases = vec![
+            (180.0, 90.0, Vector::new(0, 0, -1)),
+            (90.0, 90.0, Vector::new(1, 0, 0)),
+            (270.0, 90.0, Vector::new(-1, 0, 0)),
+            (0.0, 90.0, Vector::new(0, 0, 1)),
+            (180.0, 0.0, Vector::new(0, 1, 0)),
+            (180.0, 180.0, Vector::new(0, -1, 0)),
+        ];
+
+        for (x, y, expected) in cases {
+            let ray = projection.ray_for(resolution, x, y).unwrap();
+            assert_eq!(expected, ray.direction());
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::display::resolution::Resolution;
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::tracing::projections::projection::Projection;
+use crate::tracing::ray::Ray;
+
+/**
+ * An (equidistant) fisheye projection: the angle from straight ahead grows in proportion to the
+ * distance from the center of the image, reaching half the field of view at the edge of the
+ * largest circle fitting in the image. Nothing is seen outside that circle.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fisheye {
+    field_of_view: f64,
+}
+
+impl Fisheye {
+    pub fn new(field_of_view: f64) -> Fisheye {
+        Fisheye { field_of_view }
+    }
+}
+
+impl Projection for Fisheye {
+    fn ray_for(&self, resolution: Resolution, x: f64, y: f64) -> Option<Ray> {
+        let radius = resolution.hsize().min(resolution.vsize()) as f64 / 2.0;
+        // the point relative to the center, in units of the circle's radius (+x to the left)
+        let circle_x = (resolution.hsize() as f64 / 2.0 - x) / radius;
+        let circle_y = (resolution.vsize() as f64 / 2.0 - y) / radius;
+
+        let distance = circle_x.hypot(circle_y);
+        if distance > 1.0 {
+            return None;
+        }
+
+        let angle = distance * self.field_of_view / 2.0;
+        let direction = if distance == 0.0 {
+            Vector::new(0, 0, -1)
+        } else {
+            Vector::new(
+                angle.sin() * circle_x / distance,
+                angle.sin() * circle_y / distance,
+                -angle.cos(),
+            )
+        };
+        Some(Ray::new(Point::origin(), direction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::resolution::Resolution;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::projections::fisheye::Fisheye;
+    use crate::tracing::projections::projection::Projection;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn fisheye_looks_straight_ahead_at_center() {
+        let ray = Fisheye::new(PI)
+            .ray_for(Resolution::new(100, 100), 50.0, 50.0)
+            .unwrap();
+        assert_eq!(Vector::new(0, 0, -1), ray.direction());
+    }
+
+    #[test]
+    fn fisheye_edge_is_at_half_the_field_of_view() {
+        let projection = Fisheye::new(PI);
+        let resolution = Resolution::new(200, 100);
+
+        let top = projection.ray_for(resolution, 100.0, 0.0).unwrap();
+        assert_eq!(Vector::new(0, 1, 0), top.direction());
+
+        let left = projection.ray_for(resolution, 50.0, 50.0).unwrap();
+        assert_eq!(Vector::new(1, 0, 0), left.direction());
+    }
+
+    #[test]
+    fn nothing_is_seen_outside_the_circle() {
+        let projection = Fisheye::new(PI);
+        assert!(projection
+            .ray_for(Resolution::new(200, 100), 0.0, 0.0)
+            .is_none());
+    }
+}
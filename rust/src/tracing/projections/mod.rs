@@ -0,0 +1,5 @@
+pub mod equirectangular;
+pub mod fisheye;
+pub mod orthographic;
+pub mod perspective;
+pub mod projection;
@@ -0,0 +1,58 @@
+use crate::display::resolution::Resolution;
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::tracing::projections::projection::Projection;
+use crate::tracing::ray::Ray;
+
+/**
+ * Parallel rays fired straight ahead from a rectangle view_width units wide, so objects keep
+ * the same size however far away they are. Suits technical drawings.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Orthographic {
+    view_width: f64,
+}
+
+impl Orthographic {
+    pub fn new(view_width: f64) -> Orthographic {
+        Orthographic { view_width }
+    }
+}
+
+impl Projection for Orthographic {
+    fn ray_for(&self, resolution: Resolution, x: f64, y: f64) -> Option<Ray> {
+        let pixel_size = self.view_width / resolution.hsize() as f64;
+        let half_width = self.view_width / 2.0;
+        let half_height = pixel_size * resolution.vsize() as f64 / 2.0;
+
+        let origin = Point::at(
+            half_width - x * pixel_size,
+            half_height - y * pixel_size,
+            0.0,
+        );
+        Some(Ray::new(origin, Vector::new(0, 0, -1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::resolution::Resolution;
+    use crate::geometry::point::Point;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::projections::orthographic::Orthographic;
+    use crate::tracing::projections::projection::Projection;
+
+    #[test]
+    fn orthographic_rays_are_parallel() {
+        let projection = Orthographic::new(4.0);
+        let resolution = Resolution::new(200, 100);
+
+        let center = projection.ray_for(resolution, 100.0, 50.0).unwrap();
+        assert_eq!(Point::origin(), center.origin());
+        assert_eq!(Vector::new(0, 0, -1), center.direction());
+
+        let corner = projection.ray_for(resolution, 0.0, 0.0).unwrap();
+        assert_eq!(Point::at(2, 1, 0), corner.origin());
+        assert_eq!(Vector::new(0, 0, -1), corner.direction());
+    }
+}
@@ -0,0 +1,90 @@
+use crate::display::resolution::Resolution;
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::tracing::projections::projection::Projection;
+use crate::tracing::ray::Ray;
+
+/// The usual pinhole projection, through a canvas one unit in front of the camera.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Perspective {
+    field_of_view: f64,
+}
+
+impl Perspective {
+    pub fn new(field_of_view: f64) -> Perspective {
+        Perspective { field_of_view }
+    }
+
+    pub fn pixel_size(&self, resolution: Resolution) -> f64 {
+        self.half_width(resolution) * 2.0 / resolution.hsize() as f64
+    }
+
+    fn half_height(&self, resolution: Resolution) -> f64 {
+        let half_view = (self.field_of_view / 2.0).tan();
+
+        if resolution.aspect() >= 1.0 {
+            half_view / resolution.aspect()
+        } else {
+            half_view
+        }
+    }
+
+    fn half_width(&self, resolution: Resolution) -> f64 {
+        let half_view = (self.field_of_view / 2.0).tan();
+
+        if resolution.aspect() >= 1.0 {
+            half_view
+        } else {
+            half_view * resolution.aspect()
+        }
+    }
+}
+
+impl Projection for Perspective {
+    fn ray_for(&self, resolution: Resolution, x: f64, y: f64) -> Option<Ray> {
+        // the offset from the edge of the canvas to the point on it
+        let x_offset = x * self.pixel_size(resolution);
+        let y_offset = y * self.pixel_size(resolution);
+
+        // the coordinates of the point on the canvas, which is at z=-1
+        // (remember that the camera looks toward -z, so +x is to the left)
+        let canvas_x = self.half_width(resolution) - x_offset;
+        let canvas_y = self.half_height(resolution) - y_offset;
+
+        let direction = Vector::new(canvas_x, canvas_y, -1.0).normalize();
+        Some(Ray::new(Point::origin(), direction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::resolution::Resolution;
+    use crate::geometry::point::Point;
+    use crate::geometry::vector::Vector;
+    use crate::helpers::approximate::Approximate;
+    use crate::tracing::projections::perspective::Perspective;
+    use crate::tracing::projections::projection::Projection;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn pixel_size_for_horizontal_canvas() {
+        let projection = Perspective::new(PI / 2.0);
+        assert!(0.01.almost(projection.pixel_size(Resolution::new(200, 125))));
+    }
+
+    #[test]
+    fn pixel_size_for_vertical_canvas() {
+        let projection = Perspective::new(PI / 2.0);
+        assert!(0.01.almost(projection.pixel_size(Resolution::new(125, 200))));
+    }
+
+    #[test]
+    fn ray_through_corner_of_canvas() {
+        let ray = Perspective::new(PI / 2.0)
+            .ray_for(Resolution::new(201, 101), 0.5, 0.5)
+            .unwrap();
+
+        assert_eq!(Point::origin(), ray.origin());
+        assert_eq!(Vector::new(0.66519, 0.33259, -0.66851), ray.direction());
+    }
+}
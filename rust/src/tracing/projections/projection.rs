@@ -0,0 +1,14 @@
+use std::fmt::Debug;
+
+use crate::display::resolution::Resolution;
+use crate::tracing::ray::Ray;
+
+/// Projection is a Strategy mapping points on the image onto the rays Camera fires into the world.
+pub trait Projection: Send + Sync + Debug {
+    /**
+     * The ray through the point (x, y) of the image, measured in pixels from its top left corner.
+     * Rays are in camera space: looking toward -z, with +y up (and so +x to the left).
+     * None if the projection covers nothing at that point (e.g. outside a fisheye's circle).
+     */
+    fn ray_for(&self, resolution: Resolution, x: f64, y: f64) -> Option<Ray>;
+}
@@ -0,0 +1,140 @@
+use crate::geometry::vector::Vector;
+use rand::prelude::*;
+use std::f64::consts::PI;
+
+/// Where within each pixel Camera fires its rays. The colors seen by each are averaged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PixelSampling {
+    /// A single ray through the center of the pixel.
+    Center,
+    /// An n x n grid of rays, each through the center of its cell.
+    Grid(usize),
+    /// An n x n grid of rays, each through a random point within its cell (stratified sampling).
+    Jittered(usize),
+    /// n rays through random points anywhere within the pixel.
+    Random(usize),
+    /**
+     * One ray through the center of each pixel, then more only where a pixel's color differs
+     * from a neighbor's by more than threshold. Those pixels are split into quarters (up to
+     * max_depth times) for as long as the corners of each quarter still differ.
+     */
+    Adaptive { threshold: f64, max_depth: usize },
+}
+
+impl PixelSampling {
+    /**
+     * The offsets within the pixel to sample, as fractions (0.0 to 1.0) of its width and height.
+     * Counts of zero are treated as one, as every pixel needs at least one ray.
+     */
+    pub fn offsets(&self) -> Vec<(f64, f64)> {
+        let mut rng = thread_rng();
+        match *self {
+            // Adaptive sampling starts from the center; Camera decides where to refine it.
+            PixelSampling::Center | PixelSampling::Adaptive { .. } => vec![(0.5, 0.5)],
+            PixelSampling::Grid(size) => grid_offsets(size.max(1), || 0.5),
+            PixelSampling::Jittered(size) => grid_offsets(size.max(1), || rng.gen_range(0.0..1.0)),
+            PixelSampling::Random(count) => (0..count.max(1))
+                .map(|_| (rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0)))
+                .collect(),
+        }
+    }
+}
+
+// Offsets for each cell of a size x size grid, positioned within the cell by position_in_cell.
+fn grid_offsets(size: usize, mut position_in_cell: impl FnMut() -> f64) -> Vec<(f64, f64)> {
+    let cell_size = 1.0 / size as f64;
+    let mut offsets = Vec::with_capacity(size * size);
+    for y in 0..size {
+        for x in 0..size {
+            offsets.push((
+                (x as f64 + position_in_cell()) * cell_size,
+                (y as f64 + position_in_cell()) * cell_size,
+            ));
+        }
+    }
+    offsets
+}
+
+/**
+ * A random direction in the hemisphere around the (normalized) normal provided, more likely the
+ * nearer it is to the normal (in proportion to the cosine of the angle between them).
+ * That matches how much light arriving from each direction a diffuse surface reflects.
+ */
+pub fn cosine_weighted_direction(normal: Vector) -> Vector {
+    let mut rng = thread_rng();
+    // Uniformly sample the unit disc, then project up onto the hemisphere.
+    let radius = rng.gen_range(0.0..1.0_f64).sqrt();
+    let angle = rng.gen_range(0.0..2.0 * PI);
+    let (x, y) = (radius * angle.cos(), radius * angle.sin());
+    let z = (1.0 - x * x - y * y).max(0.0).sqrt();
+
+    // Any pair of axes perpendicular to the normal will do.
+    let helper = if normal.x.abs() > 0.9 {
+        Vector::new(0, 1, 0)
+    } else {
+        Vector::new(1, 0, 0)
+    };
+    let tangent = normal.cross(helper).normalize();
+    let bitangent = normal.cross(tangent);
+
+    (tangent * x + bitangent * y + normal * z).normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::vector::Vector;
+    use crate::helpers::approximate::Approximate;
+    use crate::tracing::sampling::{cosine_weighted_direction, PixelSampling};
+
+    #[test]
+    fn center_sampling_uses_middle_of_pixel() {
+        assert_eq!(vec![(0.5, 0.5)], PixelSampling::Center.offsets());
+    }
+
+    #[test]
+    fn grid_sampling_uses_center_of_each_cell() {
+        let offsets = PixelSampling::Grid(2).offsets();
+        assert_eq!(
+            vec![(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)],
+            offsets
+        );
+    }
+
+    #[test]
+    fn jittered_sampling_stays_within_each_cell() {
+        let offsets = PixelSampling::Jittered(3).offsets();
+        assert_eq!(9, offsets.len());
+
+        for (index, (x, y)) in offsets.into_iter().enumerate() {
+            let (cell_x, cell_y) = ((index % 3) as f64, (index / 3) as f64);
+            assert!(x >= cell_x / 3.0 && x < (cell_x + 1.0) / 3.0);
+            assert!(y >= cell_y / 3.0 && y < (cell_y + 1.0) / 3.0);
+        }
+    }
+
+    #[test]
+    fn sampling_with_zero_count_still_fires_one_ray() {
+        assert_eq!(vec![(0.5, 0.5)], PixelSampling::Grid(0).offsets());
+        assert_eq!(1, PixelSampling::Jittered(0).offsets().len());
+        assert_eq!(1, PixelSampling::Random(0).offsets().len());
+    }
+
+    #[test]
+    fn random_sampling_stays_within_pixel() {
+        let offsets = PixelSampling::Random(16).offsets();
+        assert_eq!(16, offsets.len());
+        assert!(offsets
+            .iter()
+            .all(|(x, y)| (0.0..1.0).contains(x) && (0.0..1.0).contains(y)));
+    }
+
+    #[test]
+    fn cosine_weighted_directions_lie_around_normal() {
+        let normal = Vector::new(1, 2, -1).normalize();
+        for _ in 0..100 {
+            let direction = cosine_weighted_direction(normal);
+            assert!(direction.magnitude().almost(1.0));
+            assert!(direction.dot(normal) >= 0.0);
+        }
+    }
+}
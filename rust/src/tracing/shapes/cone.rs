@@ -0,0 +1,259 @@
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::helpers::approximate;
+use crate::helpers::approximate::Approximate;
+use crate::helpers::general::OrderedTuple;
+use crate::tracing::bounding_box::BoundingBox;
+use crate::tracing::ray::Ray;
+use crate::tracing::shapes::shape::ShapeGeometry;
+use num::traits::Pow;
+
+/**
+ * A double-napped cone around the y axis, with its apex at the origin and a radius of |y|.
+ * Like Cylinder, it can be truncated to lie between min and max, and optionally capped.
+ */
+#[derive(PartialEq, Debug, Clone)]
+pub struct Cone {
+    min: f64,
+    max: f64,
+    capped: bool,
+}
+
+impl Cone {
+    pub fn new(min: f64, max: f64) -> Cone {
+        Cone {
+            min,
+            max,
+            capped: false,
+        }
+    }
+
+    pub fn infinite() -> Cone {
+        Cone::new(f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    pub fn capped(self) -> Cone {
+        Cone {
+            min: self.min,
+            max: self.max,
+            capped: true,
+        }
+    }
+
+    // Find intersections with cone end caps.
+    fn intersect_caps(&self, ray: &Ray) -> Vec<f64> {
+        if !self.capped {
+            return Vec::new();
+        }
+
+        let value = ray.direction().y;
+        if value.almost_zero() {
+            return Vec::new();
+        }
+
+        vec![self.min, self.max]
+            .into_iter()
+            .map(|y_value| (y_value, (y_value - ray.origin().y) / ray.direction().y))
+            .filter(|(y_value, time)| check_cap(ray, *time, y_value.abs()))
+            .map(|(_, time)| time)
+            .collect()
+    }
+
+    // Find intersections with the sides of the (untruncated) cone.
+    fn intersect_sides(&self, ray: &Ray) -> Vec<f64> {
+        let origin = ray.origin();
+        let direction = ray.direction();
+
+        let a: f64 = direction.x.pow(2) - direction.y.pow(2) + direction.z.pow(2);
+        let b = 2.0 * origin.x * direction.x - 2.0 * origin.y * direction.y
+            + 2.0 * origin.z * direction.z;
+        let c: f64 = origin.x.pow(2) - origin.y.pow(2) + origin.z.pow(2);
+
+        if a.almost_zero() {
+            // Parallel to one of the cone's halves, so hits (at most) the other one.
+            if b.almost_zero() {
+                return Vec::new();
+            }
+            return vec![-c / (2.0 * b)];
+        }
+
+        let discriminant: f64 = b.pow(2) - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return Vec::new();
+        }
+
+        let t0 = (-b - discriminant.sqrt()) / (2.0 * a);
+        let t1 = (-b + discriminant.sqrt()) / (2.0 * a);
+
+        let (t0, t1) = (t0, t1).ordered();
+        vec![t0, t1]
+    }
+}
+
+// Determine if the Ray would be within the cap's radius at the time supplied.
+fn check_cap(ray: &Ray, time: f64, radius: f64) -> bool {
+    let x = ray.origin().x + time * ray.direction().x;
+    let z = ray.origin().z + time * ray.direction().z;
+
+    x.pow(2) + z.pow(2) <= radius.pow(2)
+}
+
+impl ShapeGeometry for Cone {
+    fn intersect(&self, ray: &Ray) -> Vec<f64> {
+        let intersections: Vec<f64> = self
+            .intersect_sides(ray)
+            .into_iter()
+            .map(|time| (time, ray.origin().y + time * ray.direction().y))
+            .filter(|(_, y_value)| self.min < *y_value && *y_value < self.max)
+            .map(|(time, _)| time)
+            .collect();
+
+        let mut cap_intersections = self.intersect_caps(ray);
+        cap_intersections.extend_from_slice(&intersections);
+        cap_intersections
+    }
+
+    fn normal_at(&self, point: Point) -> Vector {
+        let distance: f64 = point.x.pow(2) + point.z.pow(2);
+
+        if distance < self.max.pow(2) && point.y >= self.max - approximate::EPSILON {
+            return Vector::new(0, 1, 0);
+        }
+
+        if distance < self.min.pow(2) && point.y <= self.min + approximate::EPSILON {
+            return Vector::new(0, -1, 0);
+        }
+
+        if distance.almost_zero() && point.y.almost_zero() {
+            // The apex has no well-defined normal, so point back along the axis of the
+            // nappe(s) the cone has. Lighting flips this as needed to face the viewer.
+            return if self.max <= 0.0 {
+                Vector::new(0, 1, 0)
+            } else {
+                Vector::new(0, -1, 0)
+            };
+        }
+
+        let y = if point.y > 0.0 {
+            -distance.sqrt()
+        } else {
+            distance.sqrt()
+        };
+        Vector::new(point.x, y, point.z)
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let radius = self.min.abs().max(self.max.abs());
+        BoundingBox::new(
+            Point::at(-radius, self.min, -radius),
+            Point::at(radius, self.max, radius),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::point::Point;
+    use crate::geometry::vector::Vector;
+    use crate::helpers::approximate::Approximate;
+    use crate::tracing::ray::Ray;
+    use crate::tracing::shapes::cone::Cone;
+    use crate::tracing::shapes::shape::ShapeGeometry;
+    use std::f64::consts::SQRT_2;
+
+    #[test]
+    fn intersecting_cone_with_ray() {
+        let cases = vec![
+            (Point::at(0, 0, -5), Vector::new(0, 0, 1), 5.0, 5.0),
+            (Point::at(0, 0, -5), Vector::new(1, 1, 1), 8.66025, 8.66025),
+            (
+                Point::at(1, 1, -5),
+                Vector::new(-0.5, -1.0, 1.0),
+                4.55006,
+                49.44994,
+            ),
+        ];
+
+        for (origin, direction, t1, t2) in cases {
+            let cone = Cone::infinite();
+            let ray = Ray::new(origin, direction.normalize());
+            let intersections = cone.intersect(&ray);
+            assert_eq!(2, intersections.len());
+            assert!(intersections[0].almost(t1));
+            assert!(intersections[1].almost(t2));
+        }
+    }
+
+    #[test]
+    fn intersecting_cone_with_ray_parallel_to_one_half() {
+        let cone = Cone::infinite();
+        let ray = Ray::new(Point::at(0, 0, -1), Vector::new(0, 1, 1).normalize());
+        let intersections = cone.intersect(&ray);
+        assert_eq!(1, intersections.len());
+        assert!(intersections[0].almost(0.35355));
+    }
+
+    #[test]
+    fn intersecting_cone_end_caps() {
+        let cases = vec![
+            (Point::at(0, 0, -5), Vector::new(0, 1, 0), 0),
+            (Point::at(0.0, 0.0, -0.25), Vector::new(0, 1, 1), 2),
+            (Point::at(0.0, 0.0, -0.25), Vector::new(0, 1, 0), 4),
+        ];
+
+        for (origin, direction, count) in cases {
+            let cone = Cone::new(-0.5, 0.5).capped();
+            let ray = Ray::new(origin, direction.normalize());
+            assert_eq!(count, cone.intersect(&ray).len());
+        }
+    }
+
+    #[test]
+    fn normal_vector_of_cone() {
+        let cases = vec![
+            (Point::at(1, 1, 1), Vector::new(1.0, -SQRT_2, 1.0)),
+            (Point::at(-1, -1, 0), Vector::new(-1, 1, 0)),
+        ];
+
+        for (point, normal) in cases {
+            let cone = Cone::infinite();
+            assert_eq!(normal, cone.normal_at(point));
+        }
+    }
+
+    #[test]
+    fn normal_at_apex_is_along_the_axis() {
+        assert_eq!(
+            Vector::new(0, -1, 0),
+            Cone::new(0.0, 1.0).normal_at(Point::origin())
+        );
+        assert_eq!(
+            Vector::new(0, 1, 0),
+            Cone::new(-1.0, 0.0).normal_at(Point::origin())
+        );
+    }
+
+    #[test]
+    fn normal_at_end_caps() {
+        let cases = vec![
+            (Point::at(0, -2, 0), Vector::new(0, -1, 0)),
+            (Point::at(1.5, -2.0, 0.0), Vector::new(0, -1, 0)),
+            (Point::at(0, 1, 0), Vector::new(0, 1, 0)),
+            (Point::at(0.0, 1.0, 0.5), Vector::new(0, 1, 0)),
+        ];
+
+        for (point, normal) in cases {
+            let cone = Cone::new(-2.0, 1.0).capped();
+            assert_eq!(normal, cone.normal_at(point));
+        }
+    }
+
+    #[test]
+    fn bounds_of_cone() {
+        let bounds = Cone::new(-5.0, 3.0).bounds();
+        assert_eq!(Point::at(-5, -5, -5), bounds.min());
+        assert_eq!(Point::at(5, 3, 5), bounds.max());
+
+        assert!(!Cone::infinite().bounds().is_finite());
+    }
+}
@@ -0,0 +1,335 @@
+use crate::geometry::matrix::Matrix;
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::tracing::bounding_box::BoundingBox;
+use crate::tracing::intersection::{Intersection, Intersections};
+use crate::tracing::material::Material;
+use crate::tracing::ray::Ray;
+use crate::tracing::shapes::shape::{Shape, ShapeGeometry};
+
+/// How the two Shapes of a Csg are combined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsgOperation {
+    /// Everything inside either shape.
+    Union,
+    /// Only what is inside both shapes.
+    Intersection,
+    /// What is inside the left shape but not the right.
+    Difference,
+}
+
+impl CsgOperation {
+    /**
+     * Whether an intersection survives the operation, given which shape was hit
+     * and whether the ray is currently inside each of the two shapes.
+     */
+    pub fn allows(&self, left_hit: bool, in_left: bool, in_right: bool) -> bool {
+        match self {
+            CsgOperation::Union => (left_hit && !in_right) || (!left_hit && !in_left),
+            CsgOperation::Intersection => (left_hit && in_right) || (!left_hit && in_left),
+            CsgOperation::Difference => (left_hit && !in_right) || (!left_hit && in_left),
+        }
+    }
+}
+
+/**
+ * Constructive solid geometry: two Shapes combined by a CsgOperation.
+ *
+ * Like Group, intersections are reported against the (leaf) shapes inside, so each surface
+ * keeps its own material and normal. The Csg's transformation applies on top of both.
+ */
+#[derive(Debug, Clone)]
+pub struct Csg {
+    operation: CsgOperation,
+    left: Shape,
+    right: Shape,
+}
+
+impl Csg {
+    pub fn new(operation: CsgOperation, left: Shape, right: Shape) -> Csg {
+        Csg {
+            operation,
+            left,
+            right,
+        }
+    }
+
+    pub fn union(left: Shape, right: Shape) -> Csg {
+        Csg::new(CsgOperation::Union, left, right)
+    }
+
+    pub fn intersection(left: Shape, right: Shape) -> Csg {
+        Csg::new(CsgOperation::Intersection, left, right)
+    }
+
+    pub fn difference(left: Shape, right: Shape) -> Csg {
+        Csg::new(CsgOperation::Difference, left, right)
+    }
+
+    pub fn operation(&self) -> CsgOperation {
+        self.operation
+    }
+
+    pub fn left(&self) -> &Shape {
+        &self.left
+    }
+
+    pub fn right(&self) -> &Shape {
+        &self.right
+    }
+
+    fn intersections_of(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let intersections: Vec<Intersection> = self
+            .left
+            .intersect(ray)
+            .into_iter()
+            .chain(self.right.intersect(ray).into_iter())
+            .collect();
+        self.filter_intersections(Intersections::new(intersections))
+    }
+
+    /// Keep only those intersections (sorted by time) that lie on the surface of the combined shape.
+    fn filter_intersections<'a>(&self, intersections: Intersections<'a>) -> Vec<Intersection<'a>> {
+        let mut in_left = false;
+        let mut in_right = false;
+
+        intersections
+            .into_iter()
+            .filter(|intersection| {
+                let left_hit = self.left.includes(intersection.thing());
+                let allowed = self.operation.allows(left_hit, in_left, in_right);
+
+                if left_hit {
+                    in_left = !in_left;
+                } else {
+                    in_right = !in_right;
+                }
+                allowed
+            })
+            .collect()
+    }
+}
+
+impl ShapeGeometry for Csg {
+    fn intersect(&self, ray: &Ray) -> Vec<f64> {
+        self.intersections_of(ray)
+            .iter()
+            .map(|intersection| intersection.time())
+            .collect()
+    }
+
+    fn normal_at(&self, _: Point) -> Vector {
+        panic!("Csg shapes have no surface of their own; normals must come from their children");
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.left.bounds().merge(&self.right.bounds())
+    }
+
+    // The surviving surfaces bound this Csg as a whole, which matters for refraction.
+    fn intersections<'a>(&'a self, ray: &Ray, shape: &'a Shape) -> Vec<Intersection<'a>> {
+        self.intersections_of(ray)
+            .into_iter()
+            .map(|intersection| intersection.within(shape))
+            .collect()
+    }
+
+    fn includes(&self, shape: &Shape) -> bool {
+        self.left.includes(shape) || self.right.includes(shape)
+    }
+
+    fn set_parent_transform(&mut self, transformation: &Matrix) {
+        self.left.set_parent_transform(transformation);
+        self.right.set_parent_transform(transformation);
+    }
+
+    fn set_material(&mut self, material: &Material) {
+        self.left = self.left.clone().with_material(material.clone());
+        self.right = self.right.clone().with_material(material.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::point::Point;
+    use crate::geometry::transformations::{scaling, translation};
+    use crate::geometry::vector::Vector;
+    use crate::tracing::intersection::{Intersection, Intersections};
+    use crate::tracing::material::Material;
+    use crate::tracing::ray::Ray;
+    use crate::tracing::shapes::csg::{Csg, CsgOperation};
+    use crate::tracing::shapes::cube::Cube;
+    use crate::tracing::shapes::shape::{Shape, ShapeGeometry};
+
+    #[test]
+    fn csg_is_created_with_an_operation_and_two_shapes() {
+        let left = Shape::sphere();
+        let right = Cube::new().into_shape();
+        let csg = Csg::union(left.clone(), right.clone());
+
+        assert_eq!(CsgOperation::Union, csg.operation());
+        assert_eq!(&left, csg.left());
+        assert_eq!(&right, csg.right());
+        assert_eq!(
+            CsgOperation::Intersection,
+            Csg::intersection(left.clone(), right.clone()).operation()
+        );
+        assert_eq!(
+            CsgOperation::Difference,
+            Csg::difference(left, right).operation()
+        );
+    }
+
+    #[test]
+    fn evaluating_rule_for_csg_operation() {
+        let cases = vec![
+            (CsgOperation::Union, true, true, true, false),
+            (CsgOperation::Union, true, true, false, true),
+            (CsgOperation::Union, true, false, true, false),
+            (CsgOperation::Union, true, false, false, true),
+            (CsgOperation::Union, false, true, true, false),
+            (CsgOperation::Union, false, true, false, false),
+            (CsgOperation::Union, false, false, true, true),
+            (CsgOperation::Union, false, false, false, true),
+            (CsgOperation::Intersection, true, true, true, true),
+            (CsgOperation::Intersection, true, true, false, false),
+            (CsgOperation::Intersection, true, false, true, true),
+            (CsgOperation::Intersection, true, false, false, false),
+            (CsgOperation::Intersection, false, true, true, true),
+            (CsgOperation::Intersection, false, true, false, true),
+            (CsgOperation::Intersection, false, false, true, false),
+            (CsgOperation::Intersection, false, false, false, false),
+            (CsgOperation::Difference, true, true, true, false),
+            (CsgOperation::Difference, true, true, false, true),
+            (CsgOperation::Difference, true, false, true, false),
+            (CsgOperation::Difference, true, false, false, true),
+            (CsgOperation::Difference, false, true, true, true),
+            (CsgOperation::Difference, false, true, false, true),
+            (CsgOperation::Difference, false, false, true, false),
+            (CsgOperation::Difference, false, false, false, false),
+        ];
+
+        for (operation, left_hit, in_left, in_right, expected) in cases {
+            assert_eq!(expected, operation.allows(left_hit, in_left, in_right));
+        }
+    }
+
+    #[test]
+    fn filtering_list_of_intersections() {
+        let cases = vec![
+            (CsgOperation::Union, 0, 3),
+            (CsgOperation::Intersection, 1, 2),
+            (CsgOperation::Difference, 0, 1),
+        ];
+
+        for (operation, first, second) in cases {
+            let csg = Csg::new(operation, Shape::sphere(), Cube::new().into_shape());
+            let intersections = Intersections::new(vec![
+                Intersection::new(1.0, csg.left()),
+                Intersection::new(2.0, csg.right()),
+                Intersection::new(3.0, csg.left()),
+                Intersection::new(4.0, csg.right()),
+            ]);
+            let expected = vec![intersections[first].clone(), intersections[second].clone()];
+
+            assert_eq!(expected, csg.filter_intersections(intersections));
+        }
+    }
+
+    #[test]
+    fn ray_misses_csg_object() {
+        let csg = Csg::union(Shape::sphere(), Cube::new().into_shape()).into_shape();
+        let ray = Ray::new(Point::at(0, 2, -5), Vector::new(0, 0, 1));
+        assert!(csg.intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn ray_hits_csg_object() {
+        let s1 = Shape::sphere();
+        let s2 = Shape::sphere().with_transform(translation(0.0, 0.0, 0.5));
+        let csg = Csg::union(s1.clone(), s2.clone()).into_shape();
+
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+        let intersections = csg.intersect(&ray);
+
+        assert_eq!(2, intersections.len());
+        assert_eq!(4.0, intersections[0].time());
+        assert_eq!(&s1, intersections[0].thing());
+        assert_eq!(6.5, intersections[1].time());
+        assert_eq!(&s2, intersections[1].thing());
+    }
+
+    #[test]
+    fn identical_children_are_told_apart() {
+        // Equal shapes on both sides must still be attributed to the correct one.
+        let csg = Csg::union(Shape::sphere(), Shape::sphere()).into_shape();
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+
+        let intersections = csg.intersect(&ray);
+        assert_eq!(2, intersections.len());
+        assert_eq!(4.0, intersections[0].time());
+        assert_eq!(6.0, intersections[1].time());
+    }
+
+    #[test]
+    fn difference_of_transformed_csg() {
+        let csg = Csg::difference(
+            Cube::new().into_shape(),
+            Shape::sphere().with_transform(scaling(1.2, 1.2, 1.2)),
+        )
+        .into_shape()
+        .with_transform(translation(0, 0, 10));
+
+        // Only the corners of the cube poke out of the sphere.
+        let through_middle = Ray::new(Point::origin(), Vector::new(0, 0, 1));
+        assert!(csg.intersect(&through_middle).is_empty());
+
+        let through_corner = Ray::new(Point::at(0.9, 0.9, 0.0), Vector::new(0, 0, 1));
+        let intersections = csg.intersect(&through_corner);
+        assert_eq!(2, intersections.len());
+        assert_eq!(9.0, intersections[0].time());
+        assert_eq!(
+            Vector::new(0, 0, -1),
+            intersections[0]
+                .thing()
+                .normal_at(through_corner.position(9.0))
+        );
+    }
+
+    #[test]
+    fn refraction_through_csg_uses_the_surviving_surfaces() {
+        let glass = Material::default()
+            .with_transparency(1.0)
+            .with_refractive_index(1.5);
+        let csg = Csg::intersection(
+            Shape::sphere(),
+            Shape::sphere().with_transform(translation(0.0, 0.0, 1.0)),
+        )
+        .into_shape()
+        .with_material(glass);
+
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+        let intersections = csg.intersect(&ray);
+        assert_eq!(2, intersections.len());
+
+        let entering = intersections[0].pre_computations(&ray, &intersections);
+        assert_eq!(1.0, entering.n1());
+        assert_eq!(1.5, entering.n2());
+
+        let leaving = intersections[1].pre_computations(&ray, &intersections);
+        assert_eq!(1.5, leaving.n1());
+        assert_eq!(1.0, leaving.n2());
+    }
+
+    #[test]
+    fn bounds_of_csg_contain_both_children() {
+        let csg = Csg::union(
+            Shape::sphere(),
+            Shape::sphere().with_transform(translation(3, 0, 0)),
+        );
+
+        let bounds = csg.bounds();
+        assert_eq!(Point::at(-1, -1, -1), bounds.min());
+        assert_eq!(Point::at(4, 1, 1), bounds.max());
+    }
+}
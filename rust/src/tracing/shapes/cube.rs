@@ -1,9 +1,10 @@
 use crate::geometry::point::Point;
 use crate::geometry::vector::Vector;
 use crate::helpers::approximate;
+use crate::helpers::approximate::Approximate;
+use crate::tracing::bounding_box::BoundingBox;
 use crate::tracing::ray::Ray;
 use crate::tracing::shapes::shape::ShapeGeometry;
-use crate::helpers::approximate::Approximate;
 
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub struct Cube {}
@@ -65,6 +66,10 @@ impl ShapeGeometry for Cube {
             Vector::new(0.0, 0.0, point.z)
         }
     }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Point::at(-1, -1, -1), Point::at(1, 1, 1))
+    }
 }
 
 #[cfg(test)]
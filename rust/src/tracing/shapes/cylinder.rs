@@ -3,6 +3,7 @@ use crate::geometry::vector::Vector;
 use crate::helpers::approximate;
 use crate::helpers::approximate::Approximate;
 use crate::helpers::general::OrderedTuple;
+use crate::tracing::bounding_box::BoundingBox;
 use crate::tracing::ray::Ray;
 use crate::tracing::shapes::shape::ShapeGeometry;
 use num::traits::Pow;
@@ -112,6 +113,13 @@ impl ShapeGeometry for Cylinder {
 
         Vector::new(point.x, 0.0, point.z)
     }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Point::at(-1.0, self.min, -1.0),
+            Point::at(1.0, self.max, 1.0),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -230,4 +238,13 @@ mod tests {
             assert_eq!(normal, cylinder.normal_at(point));
         }
     }
+
+    #[test]
+    fn bounds_of_cylinder() {
+        let bounds = Cylinder::new(-5.0, 3.0).bounds();
+        assert_eq!(Point::at(-1, -5, -1), bounds.min());
+        assert_eq!(Point::at(1, 3, 1), bounds.max());
+
+        assert!(!Cylinder::infinite().bounds().is_finite());
+    }
 }
@@ -0,0 +1,197 @@
+use crate::geometry::matrix::Matrix;
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::tracing::bounding_box::BoundingBox;
+use crate::tracing::bvh::Bvh;
+use crate::tracing::intersection::Intersection;
+use crate::tracing::material::Material;
+use crate::tracing::ray::Ray;
+use crate::tracing::shapes::shape::{Shape, ShapeGeometry};
+
+/**
+ * A collection of Shapes transformed as one.
+ *
+ * The group's transformation applies on top of each child's own. Intersections are reported
+ * against the children themselves, so a Group never has a surface (or normal) of its own.
+ * Children are organized into a Bvh so large meshes only test the triangles a ray is near.
+ */
+#[derive(Debug, Clone)]
+pub struct Group {
+    children: Vec<Shape>,
+    bvh: Bvh,
+}
+
+impl Group {
+    pub fn new(children: Vec<Shape>) -> Group {
+        let bvh = Bvh::build(&children);
+        Group { children, bvh }
+    }
+
+    pub fn empty() -> Group {
+        Group::new(Vec::new())
+    }
+}
+
+impl ShapeGeometry for Group {
+    fn intersect(&self, ray: &Ray) -> Vec<f64> {
+        self.bvh
+            .intersect(&self.children, ray)
+            .iter()
+            .map(|intersection| intersection.time())
+            .collect()
+    }
+
+    fn normal_at(&self, _: Point) -> Vector {
+        panic!("Groups have no surface of their own; normals must come from their children");
+    }
+
+    // Children's own transformations are fixed once grouped, so the Bvh's bounds stay valid.
+    fn bounds(&self) -> BoundingBox {
+        self.bvh.bounds()
+    }
+
+    fn intersections<'a>(&'a self, ray: &Ray, _: &'a Shape) -> Vec<Intersection<'a>> {
+        self.bvh.intersect(&self.children, ray)
+    }
+
+    fn includes(&self, shape: &Shape) -> bool {
+        self.children.iter().any(|child| child.includes(shape))
+    }
+
+    fn set_parent_transform(&mut self, transformation: &Matrix) {
+        self.children
+            .iter_mut()
+            .for_each(|child| child.set_parent_transform(transformation));
+    }
+
+    fn set_material(&mut self, material: &Material) {
+        self.children = self
+            .children
+            .drain(..)
+            .map(|child| child.with_material(material.clone()))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::geometry::point::Point;
+    use crate::geometry::transformations::{rotation_y, scaling, translation};
+    use crate::geometry::vector::Vector;
+    use crate::tracing::material::Material;
+    use crate::tracing::ray::Ray;
+    use crate::tracing::shapes::group::Group;
+    use crate::tracing::shapes::shape::{Shape, ShapeGeometry};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn intersecting_ray_with_empty_group() {
+        let group = Group::empty().into_shape();
+        let ray = Ray::new(Point::origin(), Vector::new(0, 0, 1));
+        assert!(group.intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn intersecting_ray_with_nonempty_group() {
+        let s1 = Shape::sphere();
+        let s2 = Shape::sphere().with_transform(translation(0, 0, -3));
+        let s3 = Shape::sphere().with_transform(translation(5, 0, 0));
+        let group = Group::new(vec![s1.clone(), s2.clone(), s3]).into_shape();
+
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+        let intersections = group.intersect(&ray);
+
+        assert_eq!(4, intersections.len());
+        assert_eq!(&s2, intersections[0].thing());
+        assert_eq!(&s2, intersections[1].thing());
+        assert_eq!(&s1, intersections[2].thing());
+        assert_eq!(&s1, intersections[3].thing());
+    }
+
+    #[test]
+    fn intersecting_transformed_group() {
+        let sphere = Shape::sphere().with_transform(translation(5, 0, 0));
+        let group = Group::new(vec![sphere])
+            .into_shape()
+            .with_transform(scaling(2, 2, 2));
+
+        let ray = Ray::new(Point::at(10, 0, -10), Vector::new(0, 0, 1));
+        assert_eq!(2, group.intersect(&ray).len());
+    }
+
+    #[test]
+    fn finding_normal_on_child_in_nested_groups() {
+        let sphere = Shape::sphere().with_transform(translation(5, 0, 0));
+        let inner = Group::new(vec![sphere])
+            .into_shape()
+            .with_transform(scaling(1, 2, 3));
+        let outer = Group::new(vec![inner])
+            .into_shape()
+            .with_transform(rotation_y(PI / 2.0));
+
+        // Find the child again by intersecting it, since the group owns it.
+        let ray = Ray::new(Point::at(-10, 0, -5), Vector::new(1, 0, 0));
+        let intersections = outer.intersect(&ray);
+        let child = intersections[0].thing();
+
+        let normal = child.normal_at(Point::at(1.7321, 1.1547, -5.5774));
+        assert_eq!(Vector::new(0.2857, 0.42854, -0.85716), normal);
+    }
+
+    #[test]
+    fn transforming_group_after_adding_children() {
+        // Transformations set on the group after the fact still reach the children.
+        let group = Group::new(vec![Shape::sphere()])
+            .into_shape()
+            .with_transform(translation(0, 0, 10));
+
+        let ray = Ray::new(Point::origin(), Vector::new(0, 0, 1));
+        let intersections = group.intersect(&ray);
+        let hit = intersections.hit().unwrap();
+
+        assert_eq!(9.0, hit.time());
+        assert_eq!(
+            Vector::new(0, 0, -1),
+            hit.thing().normal_at(ray.position(hit.time()))
+        );
+    }
+
+    #[test]
+    fn bounds_of_group_contain_its_children() {
+        let sphere = Shape::sphere().with_transform(translation(5, 0, 0));
+        let big_sphere = Shape::sphere().with_transform(scaling(2, 2, 2));
+        let group = Group::new(vec![sphere, big_sphere])
+            .into_shape()
+            .with_transform(translation(0, 1, 0));
+
+        let bounds = group.bounds();
+        assert_eq!(Point::at(-2, -1, -2), bounds.min());
+        assert_eq!(Point::at(6, 3, 2), bounds.max());
+    }
+
+    #[test]
+    fn group_includes_its_descendants() {
+        let inner = Group::new(vec![Shape::sphere()]).into_shape();
+        let group = Group::new(vec![inner, Shape::sphere()]).into_shape();
+
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+        let intersections = group.intersect(&ray);
+        assert!(intersections
+            .iter()
+            .all(|intersection| group.includes(intersection.thing())));
+        assert!(!group.includes(&Shape::sphere()));
+    }
+
+    #[test]
+    fn material_on_group_applies_to_children() {
+        let material = Material::default().with_color(Color::RED);
+        let group = Group::new(vec![Shape::sphere(), Shape::plane()])
+            .into_shape()
+            .with_material(material.clone());
+
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+        let intersections = group.intersect(&ray);
+        assert_eq!(&material, intersections[0].thing().material());
+    }
+}
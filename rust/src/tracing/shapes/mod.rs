@@ -1,5 +1,10 @@
+pub mod cone;
+pub mod csg;
 pub mod cube;
 pub mod cylinder;
+pub mod group;
 pub mod plane;
 pub mod shape;
+pub mod smooth_triangle;
 pub mod sphere;
+pub mod triangle;
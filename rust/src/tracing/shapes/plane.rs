@@ -1,6 +1,7 @@
 use crate::geometry::point::Point;
 use crate::geometry::vector::Vector;
 use crate::helpers::approximate;
+use crate::tracing::bounding_box::BoundingBox;
 use crate::tracing::ray::Ray;
 use crate::tracing::shapes::shape::ShapeGeometry;
 
@@ -26,13 +27,20 @@ impl ShapeGeometry for Plane {
     fn normal_at(&self, _: Point) -> Vector {
         Vector::new(0, 1, 0)
     }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Point::at(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Point::at(f64::INFINITY, 0.0, f64::INFINITY),
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::geometry::point::Point;
     use crate::geometry::vector::Vector;
-    
+
     use crate::tracing::ray::Ray;
     use crate::tracing::shapes::plane::Plane;
     use crate::tracing::shapes::shape::ShapeGeometry;
@@ -5,9 +5,10 @@ use crate::display::color::Color;
 use crate::geometry::matrix::Matrix;
 use crate::geometry::point::Point;
 use crate::geometry::vector::Vector;
+use crate::tracing::bounding_box::BoundingBox;
 use crate::tracing::intersection::{Intersection, Intersections};
+use crate::tracing::light::Light;
 use crate::tracing::material::Material;
-use crate::tracing::point_light::PointLight;
 use crate::tracing::ray::Ray;
 use crate::tracing::shapes::plane::Plane;
 use crate::tracing::shapes::sphere::Sphere;
@@ -18,6 +19,38 @@ pub type WorldShape<'a> = &'a Shape;
 pub trait ShapeGeometry: GeometryClone + Any + Send + Sync + Debug {
     fn intersect(&self, ray: &Ray) -> Vec<f64>;
     fn normal_at(&self, point: Point) -> Vector;
+    /// The bounds of this geometry, in object space.
+    fn bounds(&self) -> BoundingBox;
+
+    /// Intersections of the (object space) ray with this geometry, attributed to the owning Shape.
+    /// Geometries that know more about a hit than its time (e.g. barycentric u/v) override this.
+    fn intersections<'a>(&'a self, ray: &Ray, shape: &'a Shape) -> Vec<Intersection<'a>> {
+        self.intersect(ray)
+            .into_iter()
+            .map(|time| Intersection::new(time, shape))
+            .collect()
+    }
+
+    /// The normal at the point provided, given the u/v recorded on the Intersection.
+    /// Only geometries that interpolate their normals need to care about u/v.
+    fn normal_at_uv(&self, point: Point, _u: f64, _v: f64) -> Vector {
+        self.normal_at(point)
+    }
+
+    /// Called whenever the world transformation of the owning Shape changes.
+    /// Geometries containing other Shapes (e.g. Group) pass this on to them.
+    fn set_parent_transform(&mut self, _transformation: &Matrix) {}
+
+    /// Whether the shape provided is contained within this geometry.
+    /// Geometries containing other Shapes (e.g. Group) check them too.
+    fn includes(&self, _shape: &Shape) -> bool {
+        false
+    }
+
+    /// Called when a material is applied to the owning Shape.
+    /// Geometries containing other Shapes (e.g. Group) pass this on to them.
+    fn set_material(&mut self, _material: &Material) {}
+
     fn into_shape(self) -> Shape
     where
         Self: Sized,
@@ -38,11 +71,18 @@ pub struct Shape {
     geometry: Box<dyn ShapeGeometry>,
     material: Material,
     transformation: Matrix,
+    // The combined transformation of any Groups containing this shape.
+    parent_transformation: Matrix,
 
     // For performing computations, we really need the inverse and its transpose.
     // Because we would calculate these multiple times per ray, let's precompute them.
+    // Rays arrive already in the parent's space, so only need the local inverse.
+    // Points and normals are in world space, so need the inverse of the whole chain.
     transform_inverse: Matrix,
-    transform_inverse_transpose: Matrix,
+    world_inverse: Matrix,
+    world_inverse_transpose: Matrix,
+    // Takes world space normals back into object space (the inverse of world_inverse_transpose).
+    world_transpose: Matrix,
 }
 
 impl Shape {
@@ -59,71 +99,156 @@ impl Shape {
             geometry: Box::new(geometry),
             material: Material::default(),
             transformation: Matrix::identity(4),
+            parent_transformation: Matrix::identity(4),
             transform_inverse: Matrix::identity(4),
-            transform_inverse_transpose: Matrix::identity(4),
+            world_inverse: Matrix::identity(4),
+            world_inverse_transpose: Matrix::identity(4),
+            world_transpose: Matrix::identity(4),
         }
     }
 
     pub fn with_material(self, material: Material) -> Shape {
-        Shape {
-            material,
-            ..self
-        }
+        let mut shape = self;
+        shape.geometry.set_material(&material);
+        Shape { material, ..shape }
     }
 
     pub fn with_transform(self, transformation: Matrix) -> Shape {
-        let transform_inverse = transformation.inverse();
-        let transform_inverse_transpose = transform_inverse.transpose();
-        Shape {
+        let mut shape = Shape {
             transformation,
-            transform_inverse,
-            transform_inverse_transpose,
             ..self
-        }
+        };
+        shape.update_transforms();
+        shape
+    }
+
+    /// Place this shape inside a parent whose own (world) transformation is supplied.
+    pub(crate) fn set_parent_transform(&mut self, parent_transformation: &Matrix) {
+        self.parent_transformation = parent_transformation.clone();
+        self.update_transforms();
+    }
+
+    fn update_transforms(&mut self) {
+        let world_transformation = self.transformation.then(&self.parent_transformation);
+        self.transform_inverse = self.transformation.inverse();
+        self.world_inverse = world_transformation.inverse();
+        self.world_inverse_transpose = self.world_inverse.transpose();
+        self.world_transpose = world_transformation.transpose();
+        self.geometry.set_parent_transform(&world_transformation);
     }
 
     pub fn material(&self) -> &Material {
         &self.material
     }
 
+    /// The bounds of this shape in its parent's space (the world, unless part of a Group).
+    pub fn bounds(&self) -> BoundingBox {
+        self.geometry.bounds().transform(&self.transformation)
+    }
+
     /// Calculate when the supplied Ray intersects this shape.
     pub fn intersect(&self, ray: &Ray) -> Intersections {
         let local_ray = ray.transform(self.transform_inverse.clone());
 
-        let intersection_times = self.geometry.intersect(&local_ray);
-        let intersections = intersection_times
-            .into_iter()
-            .map(|time| Intersection::new(time, self))
-            .collect();
-
+        let intersections = self.geometry.intersections(&local_ray, self);
         Intersections::new(intersections)
     }
 
+    /// Whether the shape provided is this shape or (for Groups and the like) one of its descendants.
+    pub fn includes(&self, shape: &Shape) -> bool {
+        // Identity rather than equality, as a Csg may well combine two equal shapes.
+        std::ptr::eq(self, shape) || self.geometry.includes(shape)
+    }
+
     /// The normal vector of this shape at the point provided.
     pub fn normal_at(&self, point: Point) -> Vector {
-        let local_point = &self.transform_inverse * point;
+        let local_point = &self.world_inverse * point;
         let local_normal = self.geometry.normal_at(local_point);
-        let world_normal = &self.transform_inverse_transpose * local_normal;
+        self.to_world_normal(local_normal)
+    }
+
+    /// The normal vector of this shape at the point provided, using the u/v of the hit.
+    pub fn normal_at_uv(&self, point: Point, u: f64, v: f64) -> Vector {
+        let local_point = &self.world_inverse * point;
+        let local_normal = self.geometry.normal_at_uv(local_point, u, v);
+        self.to_world_normal(local_normal)
+    }
+
+    /**
+     * The normal to shade with at the point provided: the (world space) normal given, tilted by
+     * any bump map. Bumps are worked out in object space, so they move and scale with the shape.
+     */
+    pub fn shading_normal(&self, point: Point, normal: Vector) -> Vector {
+        match self.material.bump_map() {
+            Some(bump_map) => {
+                let local_point = &self.world_inverse * point;
+                let local_normal = (&self.world_transpose * normal).normalize();
+                self.to_world_normal(bump_map.perturb(local_point, local_normal))
+            }
+            None => normal,
+        }
+    }
+
+    fn to_world_normal(&self, local_normal: Vector) -> Vector {
+        let world_normal = &self.world_inverse_transpose * local_normal;
         world_normal.normalize()
     }
 
     pub fn lighting(
         &self,
-        light: &PointLight,
+        light: &dyn Light,
         position: Point,
         eye_vector: Vector,
         normal: Vector,
-        in_shadow: bool,
+        light_intensity: f64,
     ) -> Color {
-        let transformed_point = &self.transform_inverse * position;
-        self.material()
-            .lighting(light, transformed_point, eye_vector, normal, in_shadow)
+        let transformed_point = &self.world_inverse * position;
+        self.material().lighting(
+            light,
+            position,
+            transformed_point,
+            eye_vector,
+            normal,
+            light_intensity,
+        )
+    }
+
+    /// The ambient term of lighting alone. See Material::ambient_lighting.
+    pub fn ambient_lighting(&self, light: &dyn Light, position: Point) -> Color {
+        let transformed_point = &self.world_inverse * position;
+        self.material().ambient_lighting(light, transformed_point)
+    }
+
+    /// Lighting without the ambient term. See Material::direct_lighting.
+    pub fn direct_lighting(
+        &self,
+        light: &dyn Light,
+        position: Point,
+        eye_vector: Vector,
+        normal: Vector,
+        light_intensity: f64,
+    ) -> Color {
+        let transformed_point = &self.world_inverse * position;
+        self.material().direct_lighting(
+            light,
+            position,
+            transformed_point,
+            eye_vector,
+            normal,
+            light_intensity,
+        )
+    }
+
+    /// The color of this shape's material at the (world space) point provided.
+    pub fn color_at(&self, position: Point) -> Color {
+        let transformed_point = &self.world_inverse * position;
+        self.material().color_at(transformed_point)
     }
 }
 
 impl PartialEq for Shape {
     fn eq(&self, other: &Shape) -> bool {
-        // Originally written for tests but now used in non-test code (see Intersection::find_refractive_indexes).
+        // Originally written for tests but now used in non-test code (see Intersection::find_media).
         // Perhaps not the right way to do this since two separate instances could resolve to equal.
         // Nevertheless, two identical instances would be pointless in a World, so maybe safe to ignore.
         compare_geometries(self.geometry.as_ref(), other.geometry.as_ref())
@@ -160,16 +285,20 @@ impl Clone for Box<dyn ShapeGeometry> {
 
 #[cfg(test)]
 mod tests {
-    
+
     use std::f64::consts::PI;
 
     use crate::geometry::point::Point;
     use crate::geometry::transformations;
     use crate::geometry::vector::Vector;
-    
+
+    use crate::display::color::Color;
+    use crate::tracing::bounding_box::BoundingBox;
+    use crate::tracing::bump_map::BumpMap;
     use crate::tracing::material::Material;
+    use crate::tracing::point_light::PointLight;
     use crate::tracing::ray::Ray;
-    use crate::tracing::shapes::shape::{ShapeGeometry};
+    use crate::tracing::shapes::shape::{Shape, ShapeGeometry};
 
     #[derive(Debug, Clone, PartialEq)]
     struct TestGeometry {
@@ -201,6 +330,10 @@ mod tests {
         fn normal_at(&self, point: Point) -> Vector {
             Vector::new(point.x, point.y, point.z)
         }
+
+        fn bounds(&self) -> BoundingBox {
+            BoundingBox::new(Point::at(-1, -1, -1), Point::at(1, 1, 1))
+        }
     }
 
     #[test]
@@ -254,4 +387,31 @@ mod tests {
         let expected_normal = Vector::new(0.0, 0.97014, -0.24254);
         assert_eq!(expected_normal, actual_normal);
     }
+
+    #[test]
+    fn bumps_move_and_turn_with_the_shape() {
+        let bumpy = Material::default().with_bump_map(BumpMap::noise(0.3, 0.5));
+        let plane = Shape::plane().with_material(bumpy);
+        let point = Point::at(0.4, 0.0, 0.7);
+        let expected = plane.shading_normal(point, Vector::new(0, 1, 0));
+        assert_ne!(Vector::new(0, 1, 0), expected);
+
+        let moved = plane.clone().with_transform(
+            transformations::translation(3, 0, 0).then(&transformations::rotation_z(PI / 2.0)),
+        );
+        let moved_point = Point::at(0.0, 3.4, 0.7);
+        let actual = moved.shading_normal(moved_point, Vector::new(-1, 0, 0));
+        assert_eq!(Vector::new(-expected.y, expected.x, expected.z), actual);
+    }
+
+    #[test]
+    fn lighting_translated_shape_samples_light_from_world_point() {
+        let shape = Shape::sphere().with_transform(transformations::translation(5, 0, 0));
+        let light = PointLight::new(Point::at(5, 0, -10), Color::WHITE);
+        let eye_vector = Vector::new(0, 0, -1);
+        let normal = Vector::new(0, 0, -1);
+
+        let result = shape.lighting(&light, Point::at(5, 0, -1), eye_vector, normal, 1.0);
+        assert_eq!(Color::new(1.9, 1.9, 1.9), result);
+    }
 }
@@ -0,0 +1,121 @@
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::tracing::bounding_box::BoundingBox;
+use crate::tracing::intersection::Intersection;
+use crate::tracing::ray::Ray;
+use crate::tracing::shapes::shape::{Shape, ShapeGeometry};
+use crate::tracing::shapes::triangle::Triangle;
+
+/**
+ * A triangle with a normal at each corner. Normals are interpolated across the face
+ * using the barycentric u/v of the hit, so meshes of these appear smoothly curved.
+ */
+#[derive(PartialEq, Debug, Clone)]
+pub struct SmoothTriangle {
+    triangle: Triangle,
+    n1: Vector,
+    n2: Vector,
+    n3: Vector,
+}
+
+impl SmoothTriangle {
+    pub fn new(
+        p1: Point,
+        p2: Point,
+        p3: Point,
+        n1: Vector,
+        n2: Vector,
+        n3: Vector,
+    ) -> SmoothTriangle {
+        SmoothTriangle {
+            triangle: Triangle::new(p1, p2, p3),
+            n1,
+            n2,
+            n3,
+        }
+    }
+}
+
+impl ShapeGeometry for SmoothTriangle {
+    fn intersect(&self, ray: &Ray) -> Vec<f64> {
+        self.triangle.intersect(ray)
+    }
+
+    // Without u/v from a hit, recover them from the point itself.
+    fn normal_at(&self, point: Point) -> Vector {
+        let (u, v) = self.triangle.barycentric(point);
+        self.normal_at_uv(point, u, v)
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.triangle.bounds()
+    }
+
+    fn intersections<'a>(&'a self, ray: &Ray, shape: &'a Shape) -> Vec<Intersection<'a>> {
+        self.triangle.intersections(ray, shape)
+    }
+
+    fn normal_at_uv(&self, _: Point, u: f64, v: f64) -> Vector {
+        self.n2 * u + self.n3 * v + self.n1 * (1.0 - u - v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::point::Point;
+    use crate::geometry::vector::Vector;
+    use crate::helpers::approximate::Approximate;
+    use crate::tracing::intersection::{Intersection, Intersections};
+    use crate::tracing::ray::Ray;
+    use crate::tracing::shapes::shape::ShapeGeometry;
+    use crate::tracing::shapes::smooth_triangle::SmoothTriangle;
+
+    fn test_triangle() -> SmoothTriangle {
+        SmoothTriangle::new(
+            Point::at(0, 1, 0),
+            Point::at(-1, 0, 0),
+            Point::at(1, 0, 0),
+            Vector::new(0, 1, 0),
+            Vector::new(-1, 0, 0),
+            Vector::new(1, 0, 0),
+        )
+    }
+
+    #[test]
+    fn intersection_with_smooth_triangle_stores_uv() {
+        let shape = test_triangle().into_shape();
+        let ray = Ray::new(Point::at(-0.2, 0.3, -2.0), Vector::new(0, 0, 1));
+
+        let intersections = shape.intersect(&ray);
+        let (u, v) = intersections[0].uv().unwrap();
+        assert!(u.almost(0.45));
+        assert!(v.almost(0.25));
+    }
+
+    #[test]
+    fn smooth_triangle_uses_uv_to_interpolate_normal() {
+        let shape = test_triangle().into_shape();
+        let intersection = Intersection::with_uv(1.0, &shape, 0.45, 0.25);
+
+        let normal = intersection.normal_at(Point::origin());
+        assert_eq!(Vector::new(-0.5547, 0.83205, 0.0), normal);
+    }
+
+    #[test]
+    fn normal_without_uv_matches_interpolated_normal() {
+        let shape = test_triangle().into_shape();
+        let normal = shape.normal_at(Point::at(-0.2, 0.3, 0.0));
+        assert_eq!(Vector::new(-0.5547, 0.83205, 0.0), normal);
+    }
+
+    #[test]
+    fn preparing_normal_on_smooth_triangle() {
+        let shape = test_triangle().into_shape();
+        let intersection = Intersection::with_uv(1.0, &shape, 0.45, 0.25);
+        let ray = Ray::new(Point::at(-0.2, 0.3, -2.0), Vector::new(0, 0, 1));
+        let intersections = Intersections::new(vec![intersection.clone()]);
+
+        let details = intersection.pre_computations(&ray, &intersections);
+        assert_eq!(Vector::new(-0.5547, 0.83205, 0.0), *details.normal());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::geometry::point::Point;
 use crate::geometry::vector::Vector;
+use crate::tracing::bounding_box::BoundingBox;
 use crate::tracing::ray::Ray;
 use crate::tracing::shapes::shape::ShapeGeometry;
 
@@ -44,6 +45,10 @@ impl ShapeGeometry for Sphere {
     fn normal_at(&self, point: Point) -> Vector {
         point - self.origin()
     }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Point::at(-1, -1, -1), Point::at(1, 1, 1))
+    }
 }
 
 #[cfg(test)]
@@ -51,9 +56,9 @@ mod tests {
     use crate::geometry::point::Point;
     use crate::geometry::transformations::{rotation_z, scaling, translation};
     use crate::geometry::vector::Vector;
-    
+
     use crate::tracing::shapes::shape::{Shape, ShapeGeometry};
-    
+
     use std::f64::consts::PI;
 
     #[test]
@@ -0,0 +1,177 @@
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::helpers::approximate::Approximate;
+use crate::tracing::bounding_box::BoundingBox;
+use crate::tracing::intersection::Intersection;
+use crate::tracing::ray::Ray;
+use crate::tracing::shapes::shape::{Shape, ShapeGeometry};
+
+/**
+ * A flat triangle defined by its three corners.
+ */
+#[derive(PartialEq, Debug, Clone)]
+pub struct Triangle {
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    e1: Vector,
+    e2: Vector,
+    normal: Vector,
+}
+
+impl Triangle {
+    pub fn new(p1: Point, p2: Point, p3: Point) -> Triangle {
+        let e1 = p2 - p1;
+        let e2 = p3 - p1;
+        let normal = e2.cross(e1).normalize();
+        Triangle {
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal,
+        }
+    }
+
+    /**
+     * Möller–Trumbore intersection, producing the time along with the barycentric u and v of the hit.
+     */
+    pub fn intersect_with_uv(&self, ray: &Ray) -> Option<(f64, f64, f64)> {
+        let direction_cross_e2 = ray.direction().cross(self.e2);
+        let determinant = self.e1.dot(direction_cross_e2);
+        if determinant.almost_zero() {
+            // ray is parallel to the triangle
+            return None;
+        }
+
+        let f = 1.0 / determinant;
+        let p1_to_origin = ray.origin() - self.p1;
+        let u = f * p1_to_origin.dot(direction_cross_e2);
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let origin_cross_e1 = p1_to_origin.cross(self.e1);
+        let v = f * ray.direction().dot(origin_cross_e1);
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let time = f * self.e2.dot(origin_cross_e1);
+        Some((time, u, v))
+    }
+
+    /**
+     * The barycentric u and v of a point lying on this triangle.
+     */
+    pub fn barycentric(&self, point: Point) -> (f64, f64) {
+        let to_point = point - self.p1;
+        let d00 = self.e1.dot(self.e1);
+        let d01 = self.e1.dot(self.e2);
+        let d11 = self.e2.dot(self.e2);
+        let d20 = to_point.dot(self.e1);
+        let d21 = to_point.dot(self.e2);
+        let denominator = d00 * d11 - d01 * d01;
+
+        let u = (d11 * d20 - d01 * d21) / denominator;
+        let v = (d00 * d21 - d01 * d20) / denominator;
+        (u, v)
+    }
+}
+
+impl ShapeGeometry for Triangle {
+    fn intersect(&self, ray: &Ray) -> Vec<f64> {
+        self.intersect_with_uv(ray)
+            .map(|(time, _, _)| time)
+            .into_iter()
+            .collect()
+    }
+
+    fn normal_at(&self, _: Point) -> Vector {
+        self.normal
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::around(&[self.p1, self.p2, self.p3])
+    }
+
+    fn intersections<'a>(&'a self, ray: &Ray, shape: &'a Shape) -> Vec<Intersection<'a>> {
+        self.intersect_with_uv(ray)
+            .map(|(time, u, v)| Intersection::with_uv(time, shape, u, v))
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::point::Point;
+    use crate::geometry::vector::Vector;
+    use crate::tracing::ray::Ray;
+    use crate::tracing::shapes::shape::ShapeGeometry;
+    use crate::tracing::shapes::triangle::Triangle;
+
+    fn test_triangle() -> Triangle {
+        Triangle::new(Point::at(0, 1, 0), Point::at(-1, 0, 0), Point::at(1, 0, 0))
+    }
+
+    #[test]
+    fn constructing_a_triangle() {
+        let triangle = test_triangle();
+        assert_eq!(Vector::new(-1, -1, 0), triangle.e1);
+        assert_eq!(Vector::new(1, -1, 0), triangle.e2);
+        assert_eq!(Vector::new(0, 0, -1), triangle.normal);
+    }
+
+    #[test]
+    fn normal_of_triangle_is_constant() {
+        let triangle = test_triangle();
+        assert_eq!(
+            triangle.normal,
+            triangle.normal_at(Point::at(0.0, 0.5, 0.0))
+        );
+        assert_eq!(
+            triangle.normal,
+            triangle.normal_at(Point::at(-0.5, 0.75, 0.0))
+        );
+        assert_eq!(
+            triangle.normal,
+            triangle.normal_at(Point::at(0.5, 0.25, 0.0))
+        );
+    }
+
+    #[test]
+    fn ray_parallel_to_triangle() {
+        let ray = Ray::new(Point::at(0, -1, -2), Vector::new(0, 1, 0));
+        assert!(test_triangle().intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn ray_misses_the_edges() {
+        let cases = vec![
+            Point::at(1, 1, -2),
+            Point::at(-1, 1, -2),
+            Point::at(0, -1, -2),
+        ];
+
+        for origin in cases {
+            let ray = Ray::new(origin, Vector::new(0, 0, 1));
+            assert!(test_triangle().intersect(&ray).is_empty());
+        }
+    }
+
+    #[test]
+    fn ray_strikes_a_triangle() {
+        let ray = Ray::new(Point::at(0.0, 0.5, -2.0), Vector::new(0, 0, 1));
+        assert_eq!(vec![2.0], test_triangle().intersect(&ray));
+    }
+
+    #[test]
+    fn barycentric_coordinates_of_corners() {
+        let triangle = test_triangle();
+        assert_eq!((0.0, 0.0), triangle.barycentric(Point::at(0, 1, 0)));
+        assert_eq!((1.0, 0.0), triangle.barycentric(Point::at(-1, 0, 0)));
+        assert_eq!((0.0, 1.0), triangle.barycentric(Point::at(1, 0, 0)));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::display::color::Color;
+use crate::geometry::point::Point;
+use crate::geometry::vector::Vector;
+use crate::helpers::approximate::Approximate;
+use crate::tracing::light::{Light, LightSample};
+
+/**
+ * A point light shining only within a cone. Points within the inner angle of the cone's axis
+ * receive the full intensity, fading smoothly to nothing at the outer angle.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpotLight {
+    position: Point,
+    direction: Vector,
+    // Cosines of the half-angles, which is what the falloff actually compares against.
+    cos_inner: f64,
+    cos_outer: f64,
+    intensity: Color,
+}
+
+impl SpotLight {
+    /// A spotlight at position pointing in direction. Angles are in radians, measured from the axis.
+    pub fn new(
+        position: Point,
+        direction: Vector,
+        inner_angle: f64,
+        outer_angle: f64,
+        intensity: Color,
+    ) -> SpotLight {
+        SpotLight {
+            position,
+            direction: direction.normalize(),
+            cos_inner: inner_angle.cos(),
+            cos_outer: outer_angle.max(inner_angle).cos(),
+            intensity,
+        }
+    }
+}
+
+impl Light for SpotLight {
+    fn intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn falloff_at(&self, point: Point) -> f64 {
+        let to_point = point - self.position;
+        if to_point.magnitude().almost_zero() {
+            // No direction to measure the angle of, but the light is certainly there.
+            return 1.0;
+        }
+
+        let cos_angle = to_point.normalize().dot(self.direction);
+        if cos_angle >= self.cos_inner {
+            return 1.0;
+        }
+        if cos_angle <= self.cos_outer {
+            return 0.0;
+        }
+
+        // Smoothstep between the two edges, avoiding a visible ring at either one.
+        let t = (cos_angle - self.cos_outer) / (self.cos_inner - self.cos_outer);
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    fn samples_from(&self, point: Point) -> Vec<LightSample> {
+        vec![LightSample::between(point, self.position)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::display::color::Color;
+    use crate::geometry::point::Point;
+    use crate::geometry::vector::Vector;
+    use crate::helpers::approximate::Approximate;
+    use crate::tracing::light::Light;
+    use crate::tracing::spot_light::SpotLight;
+    use std::f64::consts::PI;
+
+    fn light() -> SpotLight {
+        SpotLight::new(
+            Point::at(0, 10, 0),
+            Vector::new(0, -1, 0),
+            PI / 8.0,
+            PI / 4.0,
+            Color::WHITE,
+        )
+    }
+
+    #[test]
+    fn spotlight_is_full_strength_inside_inner_cone() {
+        let light = light();
+        assert_eq!(1.0, light.falloff_at(Point::origin()));
+        assert_eq!(1.0, light.falloff_at(Point::at(3, 0, 0)));
+    }
+
+    #[test]
+    fn spotlight_is_dark_outside_outer_cone() {
+        let light = light();
+        assert_eq!(0.0, light.falloff_at(Point::at(11, 0, 0)));
+        assert_eq!(0.0, light.falloff_at(Point::at(0, 20, 0)));
+    }
+
+    #[test]
+    fn spotlight_fades_between_cones() {
+        let light = light();
+        let angle = 3.0 * PI / 16.0;
+        let falloff = light.falloff_at(Point::at(10.0 * angle.tan(), 0.0, 0.0));
+        assert!(falloff > 0.0 && falloff < 1.0);
+
+        let nearer = light.falloff_at(Point::at(10.0 * (angle - 0.1).tan(), 0.0, 0.0));
+        assert!(nearer > falloff);
+    }
+
+    #[test]
+    fn spotlight_is_full_strength_at_its_own_position() {
+        assert_eq!(1.0, light().falloff_at(Point::at(0, 10, 0)));
+    }
+
+    #[test]
+    fn spotlight_is_sampled_at_its_position() {
+        let samples = light().samples_from(Point::origin());
+        assert_eq!(1, samples.len());
+        assert_eq!(Vector::new(0, 1, 0), samples[0].direction());
+        assert!(samples[0].distance().almost(10.0));
+    }
+}
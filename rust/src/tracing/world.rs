@@ -1,18 +1,37 @@
 use crate::display::color::Color;
 use crate::geometry::point::Point;
 use crate::geometry::transformations::scaling;
+use crate::tracing::ambient_occlusion::AmbientOcclusion;
+use crate::tracing::backgrounds::background::Background;
+use crate::tracing::bvh::Bvh;
+use crate::tracing::dispersion::{BLUE_WAVELENGTH, GREEN_WAVELENGTH, RED_WAVELENGTH};
 use crate::tracing::intersection::{Intersection, Intersections, PreComputedIntersection};
+use crate::tracing::light::{Light, LightSample};
 use crate::tracing::material::Material;
 use crate::tracing::point_light::PointLight;
 use crate::tracing::ray::Ray;
 use crate::tracing::shapes::shape::{Shape, WorldShape};
+use rand::prelude::*;
+use std::sync::OnceLock;
 
 type BoxedShape = Shape;
 
+// Path tracing never gives up on a path before this many bounces...
+const MIN_PATH_BOUNCES: usize = 3;
+// ...and always does after this many, however bright it still is.
+const MAX_PATH_BOUNCES: usize = 32;
+
 pub struct World {
     objects: Vec<BoxedShape>,
-    light_source: PointLight,
+    // Built from objects when first needed, so must be reset whenever they change.
+    bvh: OnceLock<Bvh>,
+    light_sources: Vec<Box<dyn Light>>,
     shadows_enabled: bool,
+    ambient_occlusion: Option<AmbientOcclusion>,
+    // Seen by rays that miss everything.
+    background: Box<dyn Background>,
+    // How many rays to cast from each hit to gather diffuse light from the background, if any.
+    environment_samples: Option<usize>,
 }
 
 impl World {
@@ -23,26 +42,73 @@ impl World {
         World::new(default_spheres(), PointLight::default())
     }
 
-    pub fn new(objects: Vec<BoxedShape>, light_source: PointLight) -> World {
+    pub fn new<L: Light + 'static>(objects: Vec<BoxedShape>, light_source: L) -> World {
+        World::with_lights(objects, vec![Box::new(light_source)])
+    }
+
+    pub fn with_lights(objects: Vec<BoxedShape>, light_sources: Vec<Box<dyn Light>>) -> World {
         World {
             objects,
-            light_source,
+            bvh: OnceLock::new(),
+            light_sources,
             shadows_enabled: true,
+            ambient_occlusion: None,
+            background: Box::new(Color::BLACK),
+            environment_samples: None,
         }
     }
 
     pub fn without_shadows(self) -> World {
         World {
-            objects: self.objects,
-            light_source: self.light_source,
             shadows_enabled: false,
+            ..self
+        }
+    }
+
+    /// Scale the ambient term of lighting by how little of each hit is hidden by nearby objects.
+    pub fn with_ambient_occlusion(self, ambient_occlusion: AmbientOcclusion) -> World {
+        World {
+            ambient_occlusion: Some(ambient_occlusion),
+            ..self
+        }
+    }
+
+    /// Surround the World with the background provided, rather than darkness.
+    pub fn with_background<B: Background + 'static>(self, background: B) -> World {
+        World {
+            background: Box::new(background),
+            ..self
+        }
+    }
+
+    /**
+     * Light surfaces with the background too, as if it were a huge light around the World, by
+     * casting samples rays out from each hit. Path tracing always does this, so needs no samples.
+     */
+    pub fn with_environment_lighting(self, samples: usize) -> World {
+        World {
+            environment_samples: Some(samples),
+            ..self
         }
     }
 
     pub fn plus_shape(self, new_shape: BoxedShape) -> World {
         let mut objects = self.objects;
         objects.push(new_shape);
-        World::new(objects, self.light_source)
+        World {
+            objects,
+            bvh: OnceLock::new(),
+            ..self
+        }
+    }
+
+    pub fn plus_light<L: Light + 'static>(self, new_light: L) -> World {
+        let mut light_sources = self.light_sources;
+        light_sources.push(Box::new(new_light));
+        World {
+            light_sources,
+            ..self
+        }
     }
 
     pub fn objects(&self) -> Vec<WorldShape> {
@@ -56,29 +122,74 @@ impl World {
 
     /// Internal implementation of color_at, providing a recursion limit.
     fn color_at_internal(&self, ray: &Ray, recursion_remaining: usize) -> Color {
+        self.color_and_distance_at(ray, recursion_remaining, None).0
+    }
+
+    /**
+     * As color_at_internal, along with how far along ray the hit is (None if nothing was hit).
+     * Once dispersion has split light up, wavelength is the one color the ray carries, which
+     * sets the refractive indexes at every later hit; None is white light, not yet split.
+     */
+    fn color_and_distance_at(
+        &self,
+        ray: &Ray,
+        recursion_remaining: usize,
+        wavelength: Option<f64>,
+    ) -> (Color, Option<f64>) {
         let intersections = &self.intersected_by(ray);
         let hit = intersections.hit();
         match hit {
-            Some(hit) => self.shade_hit(
-                hit.pre_computations(ray, intersections),
-                recursion_remaining,
+            Some(hit) => (
+                self.shade_hit(
+                    at_wavelength(hit.pre_computations(ray, intersections), wavelength),
+                    recursion_remaining,
+                    wavelength,
+                ),
+                Some(hit.time() * ray.direction().magnitude()),
             ),
-            None => Color::BLACK,
+            None => (self.background_at(ray), None),
         }
     }
 
+    /// The background seen along a ray that misses everything.
+    fn background_at(&self, ray: &Ray) -> Color {
+        self.background.color_in(ray.direction().normalize())
+    }
+
     /// Determine the Color given a PreComputedIntersection.
     fn shade_hit(
         &self,
         pre_computations: PreComputedIntersection,
         recursion_remaining: usize,
+        wavelength: Option<f64>,
     ) -> Color {
-        let in_shadow = self.is_shadowed(pre_computations.over_point());
-        let light = &self.light_source;
-
-        let surface_color = pre_computations.lighting(light, in_shadow);
-        let reflected_color = self.reflect_color(&pre_computations, recursion_remaining);
-        let refracted_color = self.refracted_color(&pre_computations, recursion_remaining);
+        let unoccluded = match &self.ambient_occlusion {
+            Some(ambient_occlusion) => {
+                self.unoccluded_fraction(&pre_computations, ambient_occlusion)
+            }
+            None => 1.0,
+        };
+
+        // Each light contributes independently, with its own shadow test.
+        let surface_color = self
+            .light_sources
+            .iter()
+            .map(|light| {
+                let light_intensity =
+                    self.light_intensity_at(light.as_ref(), pre_computations.over_point());
+                pre_computations.ambient_lighting(light.as_ref()) * unoccluded
+                    + pre_computations.direct_lighting(light.as_ref(), light_intensity)
+            })
+            .fold(Color::BLACK, |total, color| total + color);
+        let surface_color = surface_color + pre_computations.material().emission();
+        let surface_color = match self.environment_samples {
+            Some(samples) => surface_color + self.environment_light(&pre_computations, samples),
+            None => surface_color,
+        };
+        let reflected_color =
+            self.reflect_color(&pre_computations, recursion_remaining, wavelength);
+        let refracted_color =
+            self.refracted_color(&pre_computations, recursion_remaining, wavelength);
 
         if pre_computations.is_reflective() && pre_computations.is_transparent() {
             let reflectance = pre_computations.schlick();
@@ -93,6 +204,7 @@ impl World {
         &self,
         pre_computations: &PreComputedIntersection,
         recursion_remaining: usize,
+        wavelength: Option<f64>,
     ) -> Color {
         if recursion_remaining == 0 {
             return Color::BLACK;
@@ -101,15 +213,20 @@ impl World {
             return Color::BLACK;
         }
 
-        let color =
-            self.color_at_internal(&pre_computations.reflect_ray(), recursion_remaining - 1);
-        pre_computations.scale_reflection(color)
+        let (color, distance) = self.color_and_distance_at(
+            &pre_computations.reflect_ray(),
+            recursion_remaining - 1,
+            wavelength,
+        );
+        pre_computations
+            .scale_reflection(color * pre_computations.reflected_transmittance(distance))
     }
 
     fn refracted_color(
         &self,
         pre_computations: &PreComputedIntersection,
         recursion_remaining: usize,
+        wavelength: Option<f64>,
     ) -> Color {
         if recursion_remaining == 0 {
             return Color::BLACK;
@@ -119,40 +236,293 @@ impl World {
             return Color::BLACK;
         }
 
+        if wavelength.is_none() && pre_computations.is_dispersive() {
+            // Each color bends by its own amount, so follow each along its own ray, just once:
+            // further on, each ray keeps to its own color rather than splitting again.
+            let color_of = |wavelength| {
+                self.refracted_color_of_one_index(
+                    &pre_computations.at_wavelength(wavelength),
+                    recursion_remaining,
+                    Some(wavelength),
+                )
+            };
+            let (red, _, _) = color_of(RED_WAVELENGTH).channels();
+            let (_, green, _) = color_of(GREEN_WAVELENGTH).channels();
+            let (_, _, blue) = color_of(BLUE_WAVELENGTH).channels();
+            return Color::new(red, green, blue);
+        }
+        self.refracted_color_of_one_index(pre_computations, recursion_remaining, wavelength)
+    }
+
+    /// The refracted color, treating all colors of light as bent by the same refractive indexes.
+    fn refracted_color_of_one_index(
+        &self,
+        pre_computations: &PreComputedIntersection,
+        recursion_remaining: usize,
+        wavelength: Option<f64>,
+    ) -> Color {
         if pre_computations.has_total_internal_reflection() {
             return Color::BLACK;
         }
 
         let refracted_ray = pre_computations.refracted_ray();
-        let color = self.color_at_internal(&refracted_ray, recursion_remaining - 1);
-        pre_computations.scale_refraction(color)
+        let (color, distance) =
+            self.color_and_distance_at(&refracted_ray, recursion_remaining - 1, wavelength);
+        pre_computations
+            .scale_refraction(color * pre_computations.refracted_transmittance(distance))
     }
 
-    fn intersected_by(&self, ray: &Ray) -> Intersections {
-        let intersections: Vec<Intersection> = self
-            .objects()
+    /**
+     * The color seen along ray by following a single random path of light back through the World.
+     * Individual paths are noisy, so average many of them (see Integrator::PathTraced).
+     */
+    pub fn path_traced_color_at(&self, ray: &Ray) -> Color {
+        self.trace_path_and_distance(ray, 0, None).0
+    }
+
+    /**
+     * The light along one random path back from ray, along with how far along ray the hit is
+     * (None if nothing was hit). wavelength is the color the path carries, as color_and_distance_at.
+     */
+    fn trace_path_and_distance(
+        &self,
+        ray: &Ray,
+        bounces: usize,
+        wavelength: Option<f64>,
+    ) -> (Color, Option<f64>) {
+        let intersections = &self.intersected_by(ray);
+        match intersections.hit() {
+            Some(hit) => (
+                self.shade_path(
+                    &at_wavelength(hit.pre_computations(ray, intersections), wavelength),
+                    bounces,
+                    wavelength,
+                ),
+                Some(hit.time() * ray.direction().magnitude()),
+            ),
+            None => (self.background_at(ray), None),
+        }
+    }
+
+    /**
+     * Light reaching the eye from the hit: that given off by the surface, that directly from each
+     * light, plus that arriving along one continuation of the path (a diffuse bounce, reflection
+     * or refraction), chosen at random in proportion to how much light each passes on.
+     * Past MIN_PATH_BOUNCES, dim surfaces may end the path instead (Russian roulette), with
+     * surviving paths weighted up to make up for it.
+     */
+    fn shade_path(
+        &self,
+        pre_computations: &PreComputedIntersection,
+        bounces: usize,
+        wavelength: Option<f64>,
+    ) -> Color {
+        let direct = self
+            .light_sources
             .iter()
-            .flat_map(|it| it.intersect(ray).into_iter())
-            .collect();
+            .map(|light| {
+                let light_intensity =
+                    self.light_intensity_at(light.as_ref(), pre_computations.over_point());
+                pre_computations.direct_lighting(light.as_ref(), light_intensity)
+            })
+            .fold(Color::BLACK, |total, color| total + color);
+        let direct = pre_computations.material().emission() + direct;
+        if bounces >= MAX_PATH_BOUNCES {
+            return direct;
+        }
+
+        let material = pre_computations.material();
+        let (reflected, refracted) =
+            if pre_computations.is_reflective() && pre_computations.is_transparent() {
+                let reflectance = pre_computations.schlick();
+                (
+                    material.reflective() * reflectance,
+                    material.transparency() * (1.0 - reflectance),
+                )
+            } else {
+                (material.reflective(), material.transparency())
+            };
+        let refracted = if pre_computations.has_total_internal_reflection() {
+            0.0
+        } else {
+            refracted
+        };
+        let diffuse_color = pre_computations.diffuse_color();
+        let diffuse = diffuse_color.max_channel();
+
+        let total = reflected + refracted + diffuse;
+        if total <= 0.0 {
+            return direct;
+        }
+        // Choosing from a range wider than total ends the path with the remaining probability.
+        let range = if bounces < MIN_PATH_BOUNCES {
+            total
+        } else {
+            total.max(1.0)
+        };
+
+        let choice = thread_rng().gen_range(0.0..range);
+        // Reflected and diffuse paths head back through whatever the light arrived through.
+        let trace_back_along = |ray: &Ray| {
+            let (color, distance) = self.trace_path_and_distance(ray, bounces + 1, wavelength);
+            color * pre_computations.reflected_transmittance(distance)
+        };
+        let indirect = if choice < reflected {
+            trace_back_along(&pre_computations.reflect_ray()) * range
+        } else if choice < reflected + refracted {
+            self.trace_refracted_path(pre_computations, bounces + 1, wavelength) * range
+        } else if choice < total {
+            trace_back_along(&pre_computations.diffuse_ray()) * diffuse_color * (range / diffuse)
+        } else {
+            Color::BLACK
+        };
+        direct + indirect
+    }
+
+    /**
+     * Light arriving along the refracted ray, faded by the medium it crosses. Where colors bend
+     * by different amounts, just one (chosen at random) is followed, weighted up to stand for all;
+     * the rest of the path then keeps to that color.
+     */
+    fn trace_refracted_path(
+        &self,
+        pre_computations: &PreComputedIntersection,
+        bounces: usize,
+        wavelength: Option<f64>,
+    ) -> Color {
+        if wavelength.is_none() && pre_computations.is_dispersive() {
+            let channel = thread_rng().gen_range(0..3);
+            let wavelength = [RED_WAVELENGTH, GREEN_WAVELENGTH, BLUE_WAVELENGTH][channel];
+            let color = self.trace_refracted_path_of_one_index(
+                &pre_computations.at_wavelength(wavelength),
+                bounces,
+                Some(wavelength),
+            );
+            let (red, green, blue) = color.channels();
+            return match channel {
+                0 => Color::new(red * 3.0, 0.0, 0.0),
+                1 => Color::new(0.0, green * 3.0, 0.0),
+                _ => Color::new(0.0, 0.0, blue * 3.0),
+            };
+        }
+        self.trace_refracted_path_of_one_index(pre_computations, bounces, wavelength)
+    }
+
+    /// As trace_refracted_path, with all colors of light bent by the same refractive indexes.
+    fn trace_refracted_path_of_one_index(
+        &self,
+        pre_computations: &PreComputedIntersection,
+        bounces: usize,
+        wavelength: Option<f64>,
+    ) -> Color {
+        if pre_computations.has_total_internal_reflection() {
+            return Color::BLACK;
+        }
+
+        let refracted_ray = pre_computations.refracted_ray();
+        let (color, distance) = self.trace_path_and_distance(&refracted_ray, bounces, wavelength);
+        color * pre_computations.refracted_transmittance(distance)
+    }
+
+    /**
+     * The grayscale ambient occlusion seen along ray: white where the hit is entirely open to
+     * the sky, darkening the more it is hidden by nearby objects. Misses are white.
+     */
+    pub fn ambient_occlusion_at(&self, ray: &Ray, ambient_occlusion: &AmbientOcclusion) -> Color {
+        let intersections = &self.intersected_by(ray);
+        match intersections.hit() {
+            Some(hit) => {
+                let pre_computations = hit.pre_computations(ray, intersections);
+                Color::WHITE * self.unoccluded_fraction(&pre_computations, ambient_occlusion)
+            }
+            None => Color::WHITE,
+        }
+    }
+
+    /// The diffuse light from the background reaching the hit, averaged over samples rays.
+    fn environment_light(
+        &self,
+        pre_computations: &PreComputedIntersection,
+        samples: usize,
+    ) -> Color {
+        if samples == 0 {
+            return Color::BLACK;
+        }
+
+        let total = (0..samples)
+            .map(|_| {
+                let ray = pre_computations.diffuse_ray();
+                if self.intersected_by(&ray).hit().is_some() {
+                    Color::BLACK
+                } else {
+                    self.background_at(&ray)
+                }
+            })
+            .fold(Color::BLACK, |total, color| total + color);
+        total * pre_computations.diffuse_color() * (1.0 / samples as f64)
+    }
+
+    /// The fraction of rays cast out across the hemisphere above the hit that escape unblocked.
+    fn unoccluded_fraction(
+        &self,
+        pre_computations: &PreComputedIntersection,
+        ambient_occlusion: &AmbientOcclusion,
+    ) -> f64 {
+        let samples = ambient_occlusion.samples();
+        if samples == 0 {
+            return 1.0;
+        }
+
+        let unoccluded = (0..samples)
+            .filter(|_| {
+                let intersections = self.intersected_by(&pre_computations.diffuse_ray());
+                !matches!(intersections.hit(), Some(hit) if hit.time() < ambient_occlusion.distance())
+            })
+            .count();
+        unoccluded as f64 / samples as f64
+    }
+
+    fn intersected_by(&self, ray: &Ray) -> Intersections {
+        let bvh = self.bvh.get_or_init(|| Bvh::build(&self.objects));
+        let intersections: Vec<Intersection> = bvh.intersect(&self.objects, ray);
         Intersections::new(intersections)
     }
 
-    /// Determine whether the given point is in shadow.
-    fn is_shadowed(&self, point: Point) -> bool {
+    /// The fraction of the light that reaches the given point: that heading its way,
+    /// scaled by the fraction of its samples not in shadow.
+    fn light_intensity_at(&self, light: &dyn Light, point: Point) -> f64 {
+        let falloff = light.falloff_at(point);
         // TODO -- move shadow toggle onto individual Shapes
-        if !self.shadows_enabled {
-            return false;
+        if !self.shadows_enabled || falloff <= 0.0 {
+            return falloff;
         }
 
-        let point_to_light = self.light_source.position() - point;
-        let distance = point_to_light.magnitude();
-        let direction = point_to_light.normalize();
+        let samples = light.samples_from(point);
+        let unshadowed = samples
+            .iter()
+            .filter(|sample| !self.is_shadowed(point, sample))
+            .count();
+        falloff * unshadowed as f64 / samples.len() as f64
+    }
 
-        let ray = Ray::new(point, direction);
+    /// Determine whether the given point is in shadow with respect to the light sample provided.
+    fn is_shadowed(&self, point: Point, sample: &LightSample) -> bool {
+        let ray = Ray::new(point, sample.direction());
 
         let intersections = self.intersected_by(&ray);
         let hit = &intersections.hit();
-        matches!(hit, Some(hit) if hit.time() < distance)
+        matches!(hit, Some(hit) if hit.time() < sample.distance())
+    }
+}
+
+/// The intersection as seen by light of wavelength, if the light has been split up.
+fn at_wavelength(
+    pre_computations: PreComputedIntersection,
+    wavelength: Option<f64>,
+) -> PreComputedIntersection {
+    match wavelength {
+        Some(wavelength) => pre_computations.at_wavelength(wavelength),
+        None => pre_computations,
     }
 }
 
@@ -180,15 +550,25 @@ mod tests {
     use crate::geometry::transformations;
     use crate::geometry::transformations::{scaling, translation};
     use crate::geometry::vector::Vector;
+    use crate::helpers::approximate::Approximate;
+    use crate::tracing::ambient_occlusion::AmbientOcclusion;
+    use crate::tracing::area_light::AreaLight;
+    use crate::tracing::backgrounds::background::Background;
+    use crate::tracing::backgrounds::vertical_gradient::VerticalGradient;
+    use crate::tracing::directional_light::DirectionalLight;
+    use crate::tracing::dispersion::{BLUE_WAVELENGTH, GREEN_WAVELENGTH, RED_WAVELENGTH};
     use crate::tracing::intersection::{Intersection, Intersections};
+    use crate::tracing::light::LightSample;
     use crate::tracing::material::Material;
     use crate::tracing::point_light::PointLight;
     use crate::tracing::ray::Ray;
     use crate::tracing::shapes::plane::Plane;
     use crate::tracing::shapes::shape::{Shape, ShapeGeometry};
     use crate::tracing::shapes::sphere::Sphere;
+    use crate::tracing::spot_light::SpotLight;
     use crate::tracing::test_helpers::TestPattern;
-    use crate::tracing::world::{default_spheres, BoxedShape, World};
+    use crate::tracing::world::{at_wavelength, default_spheres, BoxedShape, World};
+    use std::f64::consts::PI;
 
     #[test]
     fn intersect_a_world_with_a_ray() {
@@ -205,6 +585,16 @@ mod tests {
         assert_eq!(6.0, intersections[3].time());
     }
 
+    #[test]
+    fn shapes_added_after_intersecting_are_hit_too() {
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+        let world = World::empty().plus_shape(Shape::sphere());
+        assert_eq!(2, world.intersected_by(&ray).len());
+
+        let world = world.plus_shape(Shape::sphere().with_transform(translation(0, 0, 3)));
+        assert_eq!(4, world.intersected_by(&ray).len());
+    }
+
     #[test]
     fn shading_an_intersection() {
         let world = World::default();
@@ -215,7 +605,7 @@ mod tests {
 
         let comps = intersect.pre_computations(&ray, &Intersections::empty());
 
-        let color = world.shade_hit(comps, 5);
+        let color = world.shade_hit(comps, 5, None);
         assert_eq!(Color::new(0.38066, 0.47583, 0.2855), color);
     }
 
@@ -229,8 +619,8 @@ mod tests {
 
         let comps = intersect.pre_computations(&ray, &Intersections::empty());
 
-        let color = world.shade_hit(comps, 5);
-        assert_eq!(Color::new(0.975222, 0.975222, 0.975222), color);
+        let color = world.shade_hit(comps, 5, None);
+        assert_eq!(Color::new(0.90498, 0.90498, 0.90498), color);
     }
 
     #[test]
@@ -281,28 +671,104 @@ mod tests {
     fn no_shadow_when_nothing_colinear_with_point_and_light() {
         let world = World::default();
         let point = Point::at(0, 10, 0);
-        assert!(!world.is_shadowed(point));
+        let sample = LightSample::between(point, PointLight::default().position());
+        assert!(!world.is_shadowed(point, &sample));
     }
 
     #[test]
     fn shadow_when_object_point_and_light() {
         let world = World::default();
         let point = Point::at(10, -10, 10);
-        assert!(world.is_shadowed(point));
+        let sample = LightSample::between(point, PointLight::default().position());
+        assert!(world.is_shadowed(point, &sample));
     }
 
     #[test]
     fn no_shadow_when_object_behind_light() {
         let world = World::default();
         let point = Point::at(-20, 20, -20);
-        assert!(!world.is_shadowed(point));
+        let sample = LightSample::between(point, PointLight::default().position());
+        assert!(!world.is_shadowed(point, &sample));
     }
 
     #[test]
     fn no_shadow_when_object_behind_poing() {
         let world = World::default();
         let point = Point::at(-2, 2, -2);
-        assert!(!world.is_shadowed(point));
+        let sample = LightSample::between(point, PointLight::default().position());
+        assert!(!world.is_shadowed(point, &sample));
+    }
+
+    #[test]
+    fn point_lights_are_either_blocked_or_not() {
+        let world = World::default();
+        let light = PointLight::default();
+        let cases = vec![
+            (Point::at(0.0, 1.0001, 0.0), 1.0),
+            (Point::at(-1.0001, 0.0, 0.0), 1.0),
+            (Point::at(0.0, 0.0, -1.0001), 1.0),
+            (Point::at(0.0, 0.0, 1.0001), 0.0),
+            (Point::at(1.0001, 0.0, 0.0), 0.0),
+            (Point::at(0.0, -1.0001, 0.0), 0.0),
+            (Point::at(0, 0, 0), 0.0),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected, world.light_intensity_at(&light, point));
+        }
+    }
+
+    #[test]
+    fn area_lights_are_partially_blocked() {
+        let world = World::default();
+        let light = AreaLight::new(
+            Point::at(-0.5, -0.5, -5.0),
+            Vector::new(1, 0, 0),
+            2,
+            Vector::new(0, 1, 0),
+            2,
+            Color::WHITE,
+        )
+        .without_jitter();
+        let cases = vec![
+            (Point::at(0, 0, 2), 0.0),
+            (Point::at(1, -1, 2), 0.25),
+            (Point::at(1.5, 0.0, 2.0), 0.5),
+            (Point::at(1.25, 1.25, 3.0), 0.75),
+            (Point::at(0, 0, -2), 1.0),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(expected, world.light_intensity_at(&light, point));
+        }
+    }
+
+    #[test]
+    fn directional_light_is_blocked_however_far_away_the_object() {
+        let far_sphere = Shape::sphere().with_transform(translation(0, 1000, 0));
+        let world = World::new(
+            vec![far_sphere],
+            DirectionalLight::new(Vector::new(0, -1, 0), Color::WHITE),
+        );
+        let light = DirectionalLight::new(Vector::new(0, -1, 0), Color::WHITE);
+
+        assert_eq!(0.0, world.light_intensity_at(&light, Point::origin()));
+        assert_eq!(1.0, world.light_intensity_at(&light, Point::at(5, 0, 0)));
+    }
+
+    #[test]
+    fn spotlight_only_reaches_points_within_its_cone() {
+        let world = World::empty();
+        let light = SpotLight::new(
+            Point::at(0, 10, 0),
+            Vector::new(0, -1, 0),
+            PI / 8.0,
+            PI / 4.0,
+            Color::WHITE,
+        );
+
+        assert_eq!(1.0, world.light_intensity_at(&light, Point::origin()));
+        assert_eq!(0.0, world.light_intensity_at(&light, Point::at(20, 0, 0)));
     }
 
     #[test]
@@ -319,10 +785,54 @@ mod tests {
         let intersection = Intersection::new(4.0, &sphere_two);
 
         let pre_computations = intersection.pre_computations(&ray, &Intersections::empty());
-        let color = world.shade_hit(pre_computations, 5);
+        let color = world.shade_hit(pre_computations, 5, None);
         assert_eq!(Color::new(0.1, 0.1, 0.1), color);
     }
 
+    #[test]
+    fn shade_hit_sums_contribution_of_each_light() {
+        let world = World::default().plus_light(PointLight::default());
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+        let shape = world.objects.first().unwrap().clone();
+        let intersection = &shape.intersect(&ray)[0];
+
+        let comps = intersection.pre_computations(&ray, &Intersections::empty());
+        let color = world.shade_hit(comps, 5, None);
+        assert_eq!(Color::new(0.76132, 0.95166, 0.571), color);
+    }
+
+    #[test]
+    fn shade_hit_tests_shadows_separately_for_each_light() {
+        let blocked_light = PointLight::new(Point::at(0, 0, -10), Color::WHITE);
+        let clear_light = PointLight::new(Point::at(5, 0, 0), Color::new(0.5, 0.5, 0.5));
+
+        let sphere_one = Shape::sphere();
+        let sphere_two = Shape::sphere().with_transform(translation(0, 0, 10));
+        let objects: Vec<BoxedShape> = vec![sphere_one, sphere_two.clone()];
+
+        let ray = Ray::new(Point::at(0, 0, 5), Vector::new(0, 0, 1));
+        let intersection = Intersection::new(4.0, &sphere_two);
+        let shade = |world: &World| {
+            let pre_computations = intersection.pre_computations(&ray, &Intersections::empty());
+            world.shade_hit(pre_computations, 5, None)
+        };
+
+        let world = World::with_lights(
+            objects.clone(),
+            vec![
+                Box::new(blocked_light.clone()),
+                Box::new(clear_light.clone()),
+            ],
+        );
+        let blocked_only = World::new(objects.clone(), blocked_light);
+        let clear_only = World::new(objects, clear_light);
+
+        let point = Point::at(0.0, 0.0, 8.99);
+        assert!(world.is_shadowed(point, &LightSample::between(point, Point::at(0, 0, -10))));
+        assert!(!world.is_shadowed(point, &LightSample::between(point, Point::at(5, 0, 0))));
+        assert_eq!(shade(&blocked_only) + shade(&clear_only), shade(&world));
+    }
+
     #[test]
     fn reflected_color_of_non_reflective_surface() {
         let shape = Shape::sphere().with_material(Material::default().with_ambient(1.0));
@@ -331,7 +841,10 @@ mod tests {
         let intersection = Intersection::new(1.0, &shape);
         let pre_computations = intersection.pre_computations(&ray, &Intersections::empty());
 
-        assert_eq!(Color::BLACK, world.reflect_color(&pre_computations, 5))
+        assert_eq!(
+            Color::BLACK,
+            world.reflect_color(&pre_computations, 5, None)
+        )
     }
 
     #[test]
@@ -349,7 +862,7 @@ mod tests {
 
         assert_eq!(
             Color::new(0.19033, 0.23791, 0.142749),
-            world.reflect_color(&pre_computations, 5)
+            world.reflect_color(&pre_computations, 5, None)
         );
     }
 
@@ -368,8 +881,8 @@ mod tests {
         let pre_computations = intersection.pre_computations(&ray, &Intersections::empty());
 
         assert_eq!(
-            Color::new(0.84424, 0.89182, 0.79666),
-            world.shade_hit(pre_computations, 5)
+            Color::new(0.87676, 0.92434, 0.82917),
+            world.shade_hit(pre_computations, 5, None)
         );
     }
 
@@ -398,7 +911,7 @@ mod tests {
         ]);
 
         let details = intersections[0].pre_computations(&ray, &intersections);
-        assert_eq!(Color::BLACK, world.refracted_color(&details, 5));
+        assert_eq!(Color::BLACK, world.refracted_color(&details, 5, None));
     }
 
     #[test]
@@ -417,7 +930,7 @@ mod tests {
         ]);
 
         let details = intersections[0].pre_computations(&ray, &intersections);
-        assert_eq!(Color::BLACK, world.refracted_color(&details, 0));
+        assert_eq!(Color::BLACK, world.refracted_color(&details, 0, None));
     }
 
     #[test]
@@ -444,7 +957,7 @@ mod tests {
             Intersection::new(2.0_f64.sqrt() / 2.0, &outer_sphere),
         ]);
         let details = intersections[1].pre_computations(&ray, &intersections);
-        assert_eq!(Color::BLACK, world.refracted_color(&details, 5));
+        assert_eq!(Color::BLACK, world.refracted_color(&details, 5, None));
     }
 
     #[test]
@@ -478,10 +991,128 @@ mod tests {
         let details = intersections[2].pre_computations(&ray, &intersections);
         assert_eq!(
             Color::new(0.0, 0.99887, 0.04721),
-            world.refracted_color(&details, 5)
+            world.refracted_color(&details, 5, None)
         )
     }
 
+    #[test]
+    fn dispersive_refraction_follows_each_color_separately() {
+        let glass = Material::default()
+            .with_transparency(1.0)
+            .with_refractive_index(1.5);
+        let refracted_color_of = |glass: Material, wavelength: Option<f64>| {
+            let outer_sphere = Sphere::new().into_shape().with_material(
+                Material::default()
+                    .with_ambient(1.0)
+                    .with_pattern(TestPattern {}.without_transform()),
+            );
+            let inner_sphere = Sphere::new()
+                .into_shape()
+                .with_material(glass)
+                .with_transform(transformations::scaling(0.25, 0.25, 0.25));
+            let world = World::new(
+                vec![outer_sphere.clone(), inner_sphere.clone()],
+                PointLight::default(),
+            );
+
+            let ray = Ray::new(Point::at(0.0, 0.0, 0.1), Vector::new(0, 1, 0));
+            let intersections = Intersections::new(vec![
+                Intersection::new(-0.9899, &outer_sphere),
+                Intersection::new(-0.4899, &inner_sphere),
+                Intersection::new(0.4899, &inner_sphere),
+                Intersection::new(0.9899, &outer_sphere),
+            ]);
+            let details = intersections[2].pre_computations(&ray, &intersections);
+            world.refracted_color(&at_wavelength(details, wavelength), 5, wavelength)
+        };
+
+        let refracted_color_through = |glass: Material| refracted_color_of(glass, None);
+        let dispersive = glass.clone().with_abbe_number(20.0);
+        let (red, green, blue) = refracted_color_through(dispersive.clone()).channels();
+        let only_index_of = |wavelength| {
+            glass
+                .clone()
+                .with_refractive_index(dispersive.refractive_index_at(wavelength))
+        };
+        let (red_alone, _, _) = refracted_color_through(only_index_of(RED_WAVELENGTH)).channels();
+        let (_, green_alone, _) =
+            refracted_color_through(only_index_of(GREEN_WAVELENGTH)).channels();
+        let (_, _, blue_alone) = refracted_color_through(only_index_of(BLUE_WAVELENGTH)).channels();
+
+        assert!(red.almost(red_alone));
+        assert!(green.almost(green_alone));
+        assert!(blue.almost(blue_alone));
+        assert_ne!(
+            refracted_color_through(only_index_of(RED_WAVELENGTH)),
+            refracted_color_through(only_index_of(BLUE_WAVELENGTH))
+        );
+        // Light already split up keeps to its own color, all the way through.
+        assert_eq!(
+            refracted_color_through(only_index_of(RED_WAVELENGTH)),
+            refracted_color_of(dispersive.clone(), Some(RED_WAVELENGTH))
+        );
+    }
+
+    #[test]
+    fn light_reflected_inside_a_medium_is_absorbed_too() {
+        let reflected_color_inside = |material: Material| {
+            let shape = Sphere::new()
+                .into_shape()
+                .with_material(material.with_reflective(1.0));
+            let world = World::empty().plus_shape(shape.clone());
+            let ray = Ray::new(Point::origin(), Vector::new(0, 0, 1));
+            let intersections = Intersections::new(vec![
+                Intersection::new(-1.0, &shape),
+                Intersection::new(1.0, &shape),
+            ]);
+
+            let details = intersections[1].pre_computations(&ray, &intersections);
+            world.reflect_color(&details, 1, None)
+        };
+
+        let clear = reflected_color_inside(Material::default());
+        let tinted =
+            reflected_color_inside(Material::default().with_absorption(Color::new(0.5, 0.0, 0.0)));
+
+        // The reflected ray crosses the whole sphere to get back to the other side.
+        assert_ne!(Color::BLACK, clear);
+        assert_eq!(clear * Color::new((-1.0_f64).exp(), 1.0, 1.0), tinted);
+    }
+
+    #[test]
+    fn refracted_color_is_absorbed_by_thickness_of_medium() {
+        let tinted_glass = Material::default()
+            .with_ambient(0.0)
+            .with_diffuse(0.0)
+            .with_specular(0.0)
+            .with_transparency(1.0)
+            .with_refractive_index(1.5)
+            .with_absorption(Color::new(0.5, 0.0, 0.0));
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+
+        let cases: Vec<(f64, f64, f64)> = vec![(1.0, 4.0, 6.0), (2.0, 3.0, 7.0)];
+        for (radius, enter, exit) in cases {
+            let shape = Sphere::new()
+                .into_shape()
+                .with_transform(scaling(radius, radius, radius))
+                .with_material(tinted_glass.clone());
+            let world = World::empty()
+                .plus_shape(shape.clone())
+                .with_background(Color::WHITE);
+            let intersections = Intersections::new(vec![
+                Intersection::new(enter, &shape),
+                Intersection::new(exit, &shape),
+            ]);
+
+            let details = intersections[0].pre_computations(&ray, &intersections);
+            let red = (-0.5 * 2.0 * radius).exp();
+            assert_eq!(
+                Color::new(red, 1.0, 1.0),
+                world.refracted_color(&details, 5, None)
+            );
+        }
+    }
+
     #[test]
     fn shade_hit_with_transparent_material() {
         let floor = Plane::new()
@@ -512,8 +1143,8 @@ mod tests {
 
         let details = intersections[0].pre_computations(&ray, &intersections);
         assert_eq!(
-            Color::new(0.90391, 0.65391, 0.65391),
-            world.shade_hit(details, 5)
+            Color::new(0.93642, 0.68642, 0.68642),
+            world.shade_hit(details, 5, None)
         );
     }
 
@@ -548,8 +1179,187 @@ mod tests {
 
         let details = intersections[0].pre_computations(&ray, &intersections);
         assert_eq!(
-            Color::new(0.9014, 0.66392, 0.65991),
-            world.shade_hit(details, 5)
+            Color::new(0.93391, 0.69643, 0.69243),
+            world.shade_hit(details, 5, None)
+        );
+    }
+
+    #[test]
+    fn path_tracing_has_no_ambient_term() {
+        let glowing = Shape::sphere().with_material(
+            Material::default()
+                .with_ambient(1.0)
+                .with_diffuse(0.0)
+                .with_specular(0.0),
         );
+        let world = World::new(vec![glowing], PointLight::default());
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+
+        assert_eq!(Color::WHITE, world.color_at(&ray));
+        assert_eq!(Color::BLACK, world.path_traced_color_at(&ray));
+    }
+
+    #[test]
+    fn path_tracing_gathers_indirect_light() {
+        // The floor beneath the ball is in shadow, but light bounces off the floor around it
+        // and back down off the ball's underside.
+        let matte = Material::default().with_ambient(0.0).with_specular(0.0);
+        let floor = Shape::plane().with_material(matte.clone());
+        let ball = Shape::sphere()
+            .with_transform(translation(0, 2, 0))
+            .with_material(matte);
+        let world = World::new(
+            vec![floor, ball],
+            PointLight::new(Point::at(0, 10, 0), Color::WHITE),
+        );
+        let ray = Ray::new(
+            Point::at(0.0, 0.5, -5.0),
+            Vector::new(0.0, -0.5, 5.0).normalize(),
+        );
+
+        assert_eq!(Color::BLACK, world.color_at(&ray));
+        let indirect = (0..200)
+            .map(|_| world.path_traced_color_at(&ray))
+            .fold(Color::BLACK, |total, color| total + color);
+        assert!(indirect.max_channel() > 0.0);
+    }
+
+    #[test]
+    fn path_tracing_between_mirrors_terminates() {
+        let mirror = Material::default().with_reflective(1.0);
+        let floor = Shape::plane()
+            .with_transform(translation(0, -1, 0))
+            .with_material(mirror.clone());
+        let ceiling = Shape::plane()
+            .with_transform(translation(0, 1, 0))
+            .with_material(mirror);
+        let world = World::new(vec![floor, ceiling], PointLight::default());
+
+        let ray = Ray::new(Point::origin(), Vector::new(0, 1, 0));
+        world.path_traced_color_at(&ray);
+    }
+
+    #[test]
+    fn emissive_material_adds_its_glow() {
+        let glowing = Shape::sphere().with_material(
+            Material::default()
+                .with_ambient(0.0)
+                .with_diffuse(0.0)
+                .with_specular(0.0)
+                .with_emission(Color::new(0.5, 0.25, 0.0)),
+        );
+        let world = World::new(vec![glowing], PointLight::default());
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 0, 1));
+
+        assert_eq!(Color::new(0.5, 0.25, 0.0), world.color_at(&ray));
+        assert_eq!(Color::new(0.5, 0.25, 0.0), world.path_traced_color_at(&ray));
+    }
+
+    #[test]
+    fn emissive_shapes_light_the_scene_when_path_tracing() {
+        // No light sources at all; only a glowing panel above the floor.
+        let panel = Shape::plane()
+            .with_transform(translation(0, 3, 0))
+            .with_material(Material::default().with_emission(Color::WHITE));
+        let floor = Shape::plane().with_material(Material::default().with_ambient(0.0));
+        let world = World::with_lights(vec![panel, floor], vec![]);
+        let ray = Ray::new(Point::at(0, 1, -5), Vector::new(0, -1, 5).normalize());
+
+        assert_eq!(Color::BLACK, world.color_at(&ray));
+        assert!(world.path_traced_color_at(&ray).max_channel() > 0.0);
+    }
+
+    #[test]
+    fn ambient_occlusion_darkens_floor_beside_resting_ball() {
+        let ambient_only = Material::default()
+            .with_ambient(1.0)
+            .with_diffuse(0.0)
+            .with_specular(0.0);
+        let floor = Shape::plane().with_material(ambient_only);
+        let ball = Shape::sphere().with_transform(translation(0, 1, 0));
+        let world = World::new(vec![floor, ball], PointLight::default());
+        let occluded = World::new(world.objects.clone(), PointLight::default())
+            .with_ambient_occlusion(AmbientOcclusion::new(32, 10.0));
+
+        // Hits the floor just in front of where the ball touches it.
+        let ray = Ray::new(
+            Point::at(0.0, 0.1, -2.0),
+            Vector::new(0.0, -0.1, 1.5).normalize(),
+        );
+
+        assert_eq!(Color::WHITE, world.color_at(&ray));
+        assert!(occluded.color_at(&ray).max_channel() < 1.0);
+    }
+
+    #[test]
+    fn ambient_occlusion_leaves_open_surfaces_alone() {
+        let floor = Shape::plane().with_material(Material::default().with_ambient(1.0));
+        let world = World::new(vec![floor.clone()], PointLight::default());
+        let occluded = World::new(vec![floor], PointLight::default())
+            .with_ambient_occlusion(AmbientOcclusion::new(16, 10.0));
+        let ray = Ray::new(Point::at(0, 1, -5), Vector::new(0, -1, 5).normalize());
+
+        assert_eq!(world.color_at(&ray), occluded.color_at(&ray));
+        assert_eq!(
+            Color::WHITE,
+            occluded.ambient_occlusion_at(&ray, &AmbientOcclusion::new(16, 10.0))
+        );
+    }
+
+    #[test]
+    fn adding_shape_keeps_world_settings() {
+        let world = World::default()
+            .without_shadows()
+            .with_ambient_occlusion(AmbientOcclusion::new(4, 1.0))
+            .plus_shape(Shape::plane());
+
+        assert!(!world.shadows_enabled);
+        assert_eq!(Some(AmbientOcclusion::new(4, 1.0)), world.ambient_occlusion);
+        assert_eq!(3, world.objects().len());
+    }
+
+    #[test]
+    fn missed_rays_see_the_background() {
+        let world = World::default().with_background(Color::LIGHT_BLUE);
+        let ray = Ray::new(Point::at(0, 0, -5), Vector::new(0, 1, 0));
+
+        assert_eq!(Color::LIGHT_BLUE, world.color_at(&ray));
+        assert_eq!(Color::LIGHT_BLUE, world.path_traced_color_at(&ray));
+    }
+
+    #[test]
+    fn mirrors_reflect_the_background() {
+        let mirror = Material::default()
+            .with_reflective(1.0)
+            .with_ambient(0.0)
+            .with_diffuse(0.0)
+            .with_specular(0.0);
+        let world = World::new(
+            vec![Shape::plane().with_material(mirror)],
+            PointLight::default(),
+        )
+        .with_background(VerticalGradient::new(Color::BLACK, Color::WHITE));
+        let ray = Ray::new(Point::at(0, 1, -1), Vector::new(0, -1, 1).normalize());
+
+        let reflected = VerticalGradient::new(Color::BLACK, Color::WHITE)
+            .color_in(Vector::new(0, 1, 1).normalize());
+        assert_eq!(reflected, world.color_at(&ray));
+    }
+
+    #[test]
+    fn environment_lighting_lights_open_surfaces_with_the_background() {
+        let matte = Material::default()
+            .with_ambient(0.0)
+            .with_diffuse(1.0)
+            .with_specular(0.0);
+        let world = World::with_lights(vec![Shape::plane().with_material(matte)], vec![])
+            .with_background(Color::WHITE);
+        let lit = World::with_lights(world.objects.clone(), vec![])
+            .with_background(Color::WHITE)
+            .with_environment_lighting(8);
+        let ray = Ray::new(Point::at(0, 1, -5), Vector::new(0, -1, 5).normalize());
+
+        assert_eq!(Color::BLACK, world.color_at(&ray));
+        assert_eq!(Color::WHITE, lit.color_at(&ray));
     }
 }